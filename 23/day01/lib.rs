@@ -0,0 +1,201 @@
+use aoc::Rng;
+
+static INPUT: &str = include_str!("input.txt");
+
+/// The spelled digits of part 2, from one to nine.
+const SPELLED_DIGITS: &[&str] = &[
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+fn part1(input: &str) -> u32 {
+    fn parse_line(line: &str) -> u32 {
+        let mut iter = line.chars().filter_map(|c| c.to_digit(10));
+        let a = iter.next().expect("one digit");
+        // If there is a single digit in the line, it won't be encountered
+        // again since the iterator already passed over it.
+        let b = iter.next_back().unwrap_or(a);
+        a * 10 + b
+    }
+
+    input.lines().map(parse_line).sum()
+}
+
+fn part2(input: &str) -> u32 {
+    fn try_get_digit(s: &str) -> Option<u32> {
+        if let Some(digit) = s.chars().next()?.to_digit(10) {
+            return Some(digit);
+        }
+        for (i, sd) in SPELLED_DIGITS.iter().enumerate() {
+            if s.starts_with(sd) {
+                return Some(u32::try_from(i).unwrap() + 1);
+            }
+        }
+        None
+    }
+
+    fn parse_line(line: &str) -> u32 {
+        let mut iter = line.chars();
+        let mut fst = None;
+        let mut snd = None;
+
+        while !iter.as_str().is_empty() {
+            let s = iter.as_str();
+            if let Some(digit) = try_get_digit(s) {
+                fst.get_or_insert(digit);
+                snd = Some(digit);
+            }
+            iter.next();
+        }
+
+        fst.unwrap() * 10 + snd.unwrap()
+    }
+
+    input.lines().map(parse_line).sum()
+}
+
+/// `part2` in a single pass over each line, matching all the spelled digits
+/// at once with a shift-and automaton instead of trying each of them at every
+/// position.
+fn part2_automaton(input: &str) -> u32 {
+    /// The words are laid out next to each other in the bits of a state, and
+    /// the bit of each letter is set if the word matches up to it.
+    struct Automaton {
+        /// The letters of the words that are each byte.
+        masks: [u64; 256],
+        /// The first letter of every word, where a match can start.
+        firsts: u64,
+        /// The last letter of every word, where a match is complete.
+        lasts: u64,
+        /// The digit of the word whose last letter is each bit.
+        digits: [u32; 64],
+    }
+
+    impl Automaton {
+        fn new() -> Self {
+            let mut automaton = Automaton {
+                masks: [0; 256],
+                firsts: 0,
+                lasts: 0,
+                digits: [0; 64],
+            };
+            let mut bit = 0;
+            for (digit, word) in (1..).zip(SPELLED_DIGITS) {
+                automaton.firsts |= 1 << bit;
+                for &b in word.as_bytes() {
+                    automaton.masks[usize::from(b)] |= 1 << bit;
+                    bit += 1;
+                }
+                automaton.lasts |= 1 << (bit - 1);
+                automaton.digits[bit - 1] = digit;
+            }
+            automaton
+        }
+
+        /// The digits in the line, spelled or not, in order.
+        fn digits<'a>(&'a self, line: &'a str) -> impl Iterator<Item = u32> + 'a {
+            let mut state = 0_u64;
+            line.bytes().filter_map(move |b| {
+                // A match can't go on from the last letter of a word into the
+                // first letter of the next one.
+                state = (((state << 1) & !self.firsts) | self.firsts) & self.masks[usize::from(b)];
+                let matched = state & self.lasts;
+                if matched != 0 {
+                    // Words can't end at the same letter, so it's a single bit.
+                    return Some(self.digits[matched.trailing_zeros() as usize]);
+                }
+                char::from(b).to_digit(10)
+            })
+        }
+    }
+
+    let automaton = Automaton::new();
+    input
+        .lines()
+        .map(|line| {
+            let mut digits = automaton.digits(line);
+            let first = digits.next().expect("one digit");
+            let last = digits.last().unwrap_or(first);
+            first * 10 + last
+        })
+        .sum()
+}
+
+/// Generates `size` lines of letters, digits and spelled digits, each with at
+/// least one digit (which part 1 needs).
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let digit = |rng: &mut Rng| char::from(b'1' + u8::try_from(rng.range(0..9)).unwrap());
+
+    let mut input = String::new();
+    for _ in 0..size {
+        let mut line = String::new();
+        let mut has_digit = false;
+        for _ in 0..rng.range(1..20) {
+            if rng.one_in(4) {
+                let word = rng.choose(SPELLED_DIGITS);
+                line.push_str(word);
+            } else if rng.one_in(3) {
+                line.push(digit(rng));
+                has_digit = true;
+            } else {
+                for _ in 0..rng.range(1..6) {
+                    line.push(char::from(b'a' + u8::try_from(rng.range(0..26)).unwrap()));
+                }
+            }
+        }
+        if !has_digit {
+            let at = rng.index(line.len() + 1);
+            line.insert(at, digit(rng));
+        }
+        input.push_str(&line);
+        input.push('\n');
+    }
+    input
+}
+
+pub static SOLUTION: aoc::Solution = aoc::Solution::new(
+    INPUT,
+    |input, _| part1(input).to_string(),
+    |input, _| part2(input).to_string(),
+)
+.with_variants(&[aoc::Variant::new(
+    "automaton",
+    aoc::Part::Two,
+    |input, _| part2_automaton(input).to_string(),
+)])
+.with_generator(generate)
+.with_answers([Some("54877"), Some("54100")])
+.with_examples(&[
+    aoc::Example {
+        input: include_str!("example1.txt"),
+        answers: [Some("142"), None],
+    },
+    aoc::Example {
+        input: include_str!("example2.txt"),
+        answers: [None, Some("281")],
+    },
+]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        aoc::check_examples(&SOLUTION, aoc::Part::One);
+    }
+
+    #[test]
+    fn test_part2() {
+        aoc::check_examples(&SOLUTION, aoc::Part::Two);
+    }
+
+    #[test]
+    fn test_generate() {
+        aoc::check_generate(&SOLUTION);
+    }
+
+    #[test]
+    fn test_answers() {
+        aoc::check_answers(&SOLUTION);
+    }
+}
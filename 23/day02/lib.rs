@@ -0,0 +1,503 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Write as _},
+    hint::black_box,
+};
+
+use aoc::{
+    enum_map::{Enum, EnumMap},
+    Context, Param, Rng,
+};
+
+static INPUT: &str = include_str!("input.txt");
+
+fn part1(input: &str, ctx: &Context) -> u32 {
+    let record = parse_record(input).expect("valid record");
+    let bag = Bag::from_context(ctx);
+    feasible_games(&record, &bag).map(|game| game.id).sum()
+}
+
+fn part2(input: &str) -> u32 {
+    let record = parse_record(input).expect("valid record");
+    record
+        .games
+        .iter()
+        .map(|game| record.palette.power(&minimal_bag(&record, game)))
+        .sum()
+}
+
+/// Parses the games, failing on a syntax error or on more than
+/// [`MAX_COLORS`] colors.
+fn parse_record(input: &str) -> Result<Record, String> {
+    let mut palette = Palette::default();
+    let games = input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| match parsers::game(line, &mut palette) {
+            Ok((_, game)) => Ok(game),
+            Err(nom::Err::Failure(e)) if e.code == nom::error::ErrorKind::TooLarge => Err(format!(
+                "line {}: more than {MAX_COLORS} colors, from `{}`",
+                i + 1,
+                e.input
+            )),
+            Err(e) => Err(format!("line {}: {e}", i + 1)),
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(Record { palette, games })
+}
+
+/// Returns the minimal bag of each game: the fewest cubes of each color that
+/// make it possible, keyed by the color names. The colors a game never draws
+/// are left out.
+///
+/// # Panics
+///
+/// If the input isn't a valid record.
+#[must_use]
+pub fn minimal_bags(input: &str) -> Vec<Bag> {
+    let record = parse_record(input).expect("valid record");
+    record
+        .games
+        .iter()
+        .map(|game| minimal_bag(&record, game))
+        .collect()
+}
+
+/// Returns the games that could have been played with the given bag.
+fn feasible_games<'a>(record: &'a Record, bag: &Bag) -> impl Iterator<Item = &'a Game> {
+    let limits = record.palette.cubes(bag);
+    record.games.iter().filter(move |game| {
+        game.sets
+            .iter()
+            .all(|set| set.cubes().all_with(&limits, |used, limit| used <= limit))
+    })
+}
+
+/// Returns the fewest cubes of each color that make the game possible.
+fn minimal_cubes(game: &Game) -> Cubes {
+    game.sets
+        .iter()
+        .fold(Cubes::default(), |acc, set| acc.max(&set.cubes()))
+}
+
+/// Same as [`minimal_cubes`], but as a bag.
+fn minimal_bag(record: &Record, game: &Game) -> Bag {
+    record.palette.bag(&minimal_cubes(game))
+}
+
+/// Generates `size` games of up to 6 sets, drawing from the colors of the
+/// puzzle statement.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    for id in 1..=size {
+        let sets: Vec<String> = (0..rng.range(1..7))
+            .map(|_| {
+                let mut colors = ["red", "green", "blue"];
+                rng.shuffle(&mut colors);
+                let drawn = rng.index(colors.len()) + 1;
+                colors[..drawn]
+                    .iter()
+                    .map(|color| format!("{} {color}", rng.range(1..21)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .collect();
+        let _ = writeln!(input, "Game {id}: {}", sets.join("; "));
+    }
+    input
+}
+
+pub static SOLUTION: aoc::Solution = aoc::Solution::new(
+    INPUT,
+    |input, ctx| part1(input, ctx).to_string(),
+    |input, _| part2(input).to_string(),
+)
+.with_parse(|input| {
+    black_box(parse_record(input).unwrap());
+})
+.with_generator(generate)
+// The bag from the puzzle statement. Other colors may be added (e.g., with
+// `--param violet=3`).
+.with_params(&[
+    Param::new("red", "12"),
+    Param::new("green", "13"),
+    Param::new("blue", "14"),
+])
+.with_extra_params()
+.with_invariants(&[aoc::Invariant::new(
+    "there are at most 8 colors",
+    fits_palette,
+)])
+.with_answers([Some("1931"), Some("83105")])
+.with_examples(&[aoc::Example {
+    input: include_str!("example1.txt"),
+    answers: [Some("8"), Some("2286")],
+}]);
+
+/// How many distinct colors an input may have. The cubes of each color are
+/// counted in [`EnumMap`]s, with a slot per [`Color`], so this is a limit of
+/// the solution rather than of the puzzle: inputs with more colors break the
+/// invariant, and fail to parse.
+const MAX_COLORS: usize = 8;
+
+/// [`Color`] can't tell apart more than [`MAX_COLORS`] colors.
+fn fits_palette(input: &str) -> Result<(), String> {
+    let mut names = Vec::new();
+    for line in input.lines() {
+        let draws = line.split([':', ';', ',']).skip(1);
+        for (_, name) in draws.filter_map(|draw| draw.trim().split_once(' ')) {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+    if names.len() > MAX_COLORS {
+        return Err(format!("{} colors: {}", names.len(), names.join(", ")));
+    }
+    Ok(())
+}
+
+/// A color name interned in a [`Palette`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Color(u8);
+
+impl Enum for Color {
+    const LEN: usize = MAX_COLORS;
+
+    type Array<V> = [V; MAX_COLORS];
+
+    fn array_from_fn<V>(f: impl FnMut(usize) -> V) -> Self::Array<V> {
+        std::array::from_fn(f)
+    }
+
+    fn from_index(index: usize) -> Self {
+        Color(u8::try_from(index).unwrap())
+    }
+
+    fn to_index(self) -> usize {
+        usize::from(self.0)
+    }
+}
+
+/// Number of cubes of each color.
+type Cubes = EnumMap<Color, u32>;
+
+/// Number of cubes drawn of each color, or `None` if the color wasn't drawn at
+/// all, which tells `0 red` apart from no red.
+type Draws = EnumMap<Color, Option<u32>>;
+
+/// The color names seen in an input, in order of appearance.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Palette {
+    names: Vec<String>,
+}
+
+impl Palette {
+    /// Returns `None` if the palette is already full.
+    fn intern(&mut self, name: &str) -> Option<Color> {
+        if let Some(i) = self.names.iter().position(|n| n == name) {
+            return Some(Color::from_index(i));
+        }
+        if self.names.len() == MAX_COLORS {
+            return None;
+        }
+        self.names.push(name.to_owned());
+        Some(Color::from_index(self.names.len() - 1))
+    }
+
+    fn name(&self, color: Color) -> &str {
+        &self.names[color.to_index()]
+    }
+
+    fn colors(&self) -> impl Iterator<Item = Color> {
+        (0..self.names.len()).map(Color::from_index)
+    }
+
+    /// Multiplies the counts of every color in the palette, so a color that
+    /// isn't in the bag makes the power zero.
+    fn power(&self, bag: &Bag) -> u32 {
+        self.colors()
+            .map(|color| bag.get(self.name(color)))
+            .product()
+    }
+
+    /// Looks up the palette colors in the bag.
+    fn cubes(&self, bag: &Bag) -> Cubes {
+        self.colors()
+            .map(|color| (color, bag.get(self.name(color))))
+            .collect()
+    }
+
+    /// Builds a bag with the palette colors that have at least one cube.
+    fn bag(&self, cubes: &Cubes) -> Bag {
+        self.colors()
+            .filter(|&color| cubes[color] > 0)
+            .map(|color| (self.name(color).to_owned(), cubes[color]))
+            .collect()
+    }
+}
+
+/// How many cubes of each color the bag holds. Colors not in the bag have no
+/// cubes at all.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Bag {
+    cubes: HashMap<String, u32>,
+}
+
+impl FromIterator<(String, u32)> for Bag {
+    fn from_iter<T: IntoIterator<Item = (String, u32)>>(iter: T) -> Self {
+        Bag {
+            cubes: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Bag {
+    /// The number of cubes of the color.
+    #[must_use]
+    pub fn get(&self, color: &str) -> u32 {
+        self.cubes.get(color).copied().unwrap_or(0)
+    }
+
+    /// Builds a bag with a color for each parameter, whose value is the number
+    /// of cubes of that color.
+    fn from_context(ctx: &Context) -> Self {
+        ctx.params()
+            .map(|(color, _)| (color.to_owned(), ctx.param(color)))
+            .collect()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Record {
+    palette: Palette,
+    games: Vec<Game>,
+}
+
+/// Prints the games back as in the input. The colors drawn in each set are
+/// printed in the order of the palette.
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for game in &self.games {
+            write!(f, "Game {}: ", game.id)?;
+            for (i, set) in game.sets.iter().enumerate() {
+                if i > 0 {
+                    f.write_str("; ")?;
+                }
+                let drawn = self
+                    .palette
+                    .colors()
+                    .filter_map(|color| Some((color, set.drawn[color]?)));
+                for (j, (color, count)) in drawn.enumerate() {
+                    if j > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{count} {}", self.palette.name(color))?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Set {
+    drawn: Draws,
+}
+
+impl Set {
+    fn cubes(&self) -> Cubes {
+        EnumMap::from_fn(|color| self.drawn[color].unwrap_or(0))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Game {
+    id: u32,
+    sets: Vec<Set>,
+}
+
+mod parsers {
+    use nom::{
+        bytes::complete::tag,
+        character::complete::{alpha1, digit1},
+        combinator::{eof, map_res, recognize},
+        error::{Error, ErrorKind},
+        multi::separated_list1,
+        sequence::tuple,
+        Err, IResult,
+    };
+
+    use super::*;
+
+    // Game 51: 2 green, 6 blue; 1 green, 10 blue, 1 red; 3 blue, 2 green
+    pub fn game<'a>(input: &'a str, palette: &mut Palette) -> IResult<&'a str, Game> {
+        let (input, (_, id, _, sets, _)) = tuple((
+            //
+            tag("Game "),
+            number,
+            tag(": "),
+            |i| sets(i, palette),
+            eof,
+        ))(input)?;
+        Ok((input, Game { id, sets }))
+    }
+
+    // 2 green, 6 blue; 1 green, 10 blue, 1 red; 3 blue, 2 green
+    fn sets<'a>(input: &'a str, palette: &mut Palette) -> IResult<&'a str, Vec<Set>> {
+        separated_list1(tag("; "), |i| set(i, palette))(input)
+    }
+
+    // 2 green, 6 blue
+    fn set<'a>(input: &'a str, palette: &mut Palette) -> IResult<&'a str, Set> {
+        let mut drawn = Draws::default();
+        let mut add = |i: &'a str| {
+            let (rest, (name, count)) = color_count(i)?;
+            let Some(color) = palette.intern(name) else {
+                return Err(Err::Failure(Error::new(i, ErrorKind::TooLarge)));
+            };
+            *drawn[color].get_or_insert(0) += count;
+            Ok(rest)
+        };
+
+        let mut input = add(input)?;
+        while let Ok((rest, _)) = tag::<_, _, Error<_>>(", ")(input) {
+            input = add(rest)?;
+        }
+        Ok((input, Set { drawn }))
+    }
+
+    // 2 green
+    fn color_count(input: &str) -> IResult<&str, (&str, u32)> {
+        let (input, (count, _, color)) = tuple((number, tag(" "), alpha1))(input)?;
+        Ok((input, (color, count)))
+    }
+
+    fn number(input: &str) -> IResult<&str, u32> {
+        map_res(recognize(digit1), str::parse)(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        aoc::check_examples(&SOLUTION, aoc::Part::One);
+    }
+
+    #[test]
+    fn test_part2() {
+        aoc::check_examples(&SOLUTION, aoc::Part::Two);
+    }
+
+    #[test]
+    fn test_bag_from_context() {
+        let mut ctx = SOLUTION.context(false);
+        ctx.set("red", "20").unwrap();
+        ctx.set("violet", "3").unwrap();
+        let bag = Bag::from_context(&ctx);
+        assert_eq!(bag.cubes["red"], 20);
+        assert_eq!(bag.cubes["green"], 13);
+        assert_eq!(bag.cubes["violet"], 3);
+    }
+
+    #[test]
+    fn test_custom_colors() {
+        let input = "\
+Game 1: 2 violet, 1 red; 3 violet
+Game 2: 1 red, 1 green";
+        let record = parse_record(input).unwrap();
+        let bag = [("violet".to_owned(), 3), ("red".to_owned(), 1)]
+            .into_iter()
+            .collect();
+        let feasible: Vec<_> = feasible_games(&record, &bag).map(|g| g.id).collect();
+        assert_eq!(feasible, [1]);
+        let [violet, green]: [Bag; 2] = minimal_bags(input).try_into().unwrap();
+        assert_eq!(violet, bag);
+        assert_eq!((green.get("green"), green.get("violet")), (1, 0));
+
+        // Game 1 never draws green, and game 2 never draws violet.
+        assert_eq!(part2(input), 0);
+    }
+
+    #[test]
+    fn test_too_many_colors() {
+        let colors = ["a", "b", "c", "d", "e", "f", "g", "h", "i"];
+        let sets: Vec<_> = colors.iter().map(|c| format!("1 {c}")).collect();
+        let input = format!("Game 1: {}", sets[..8].join("; "));
+        assert_eq!(fits_palette(&input), Ok(()));
+        let input = format!("Game 1: {}\nGame 2: {}", sets[..8].join("; "), sets[8]);
+        assert_eq!(
+            fits_palette(&input),
+            Err("9 colors: a, b, c, d, e, f, g, h, i".to_owned())
+        );
+        assert_eq!(
+            parse_record(&input).unwrap_err(),
+            "line 2: more than 8 colors, from `1 i`"
+        );
+        assert!(parse_record("Game 1: 1").is_err());
+    }
+
+    /// The games of a record, with the color of each count as an index into
+    /// the palette, as interned by the parser.
+    fn record() -> impl Strategy<Value = Record> {
+        let names = prop::collection::hash_set("[a-z]{1,8}", 1..=MAX_COLORS);
+        let set = prop::collection::vec((any::<prop::sample::Index>(), 0..1000_u32), 1..4);
+        let game = (any::<u32>(), prop::collection::vec(set, 1..5));
+        (names, prop::collection::vec(game, 0..5)).prop_map(|(names, games)| {
+            let names: Vec<String> = names.into_iter().collect();
+            let mut palette = Palette::default();
+            let games = games
+                .into_iter()
+                .map(|(id, sets)| {
+                    let sets = sets
+                        .into_iter()
+                        .map(|counts| {
+                            let mut drawn = Draws::default();
+                            for (name, count) in counts {
+                                let color =
+                                    palette.intern(&names[name.index(names.len())]).unwrap();
+                                *drawn[color].get_or_insert(0) += count;
+                            }
+                            Set { drawn }
+                        })
+                        .collect();
+                    Game { id, sets }
+                })
+                .collect();
+            Record { palette, games }
+        })
+    }
+
+    #[test]
+    fn test_print_zeros() {
+        // A zero is still a draw, so it's printed and keeps blue first in the
+        // palette.
+        let input = "Game 1: 0 blue, 2 red\nGame 2: 0 red\n";
+        let record = parse_record(input).unwrap();
+        assert_eq!(record.palette.names, ["blue", "red"]);
+        assert_eq!(record.to_string(), input);
+    }
+
+    proptest! {
+        #[test]
+        fn test_print_parse(record in record()) {
+            prop_assert_eq!(parse_record(&record.to_string()), Ok(record));
+        }
+    }
+
+    #[test]
+    fn test_generate() {
+        aoc::check_generate(&SOLUTION);
+    }
+
+    #[test]
+    fn test_answers() {
+        aoc::check_answers(&SOLUTION);
+    }
+}
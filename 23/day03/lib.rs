@@ -1,9 +1,12 @@
 use std::{
     collections::{HashMap, HashSet},
+    hint::black_box,
     ops::Index,
     str::FromStr,
 };
 
+use aoc::Rng;
+
 static INPUT: &str = include_str!("input.txt");
 
 fn part1(input: &str) -> u32 {
@@ -12,11 +15,8 @@ fn part1(input: &str) -> u32 {
     let mut sum = 0;
 
     let mut iter = grid.entries().peekable();
-    loop {
-        // Stop the iteration when there are no elements left.
-        let Some((coord @ (row, _), value)) = iter.next() else {
-            break;
-        };
+    // Stop the iteration when there are no elements left.
+    while let Some((coord @ (row, _), value)) = iter.next() {
         // The rest of the loop accumulates over digits, so skip non digits.
         let Value::Digit(digit) = value else {
             continue;
@@ -29,12 +29,9 @@ fn part1(input: &str) -> u32 {
         // of some special value, so we better also keep track of it.
         let mut seen_special = grid.has_special_neighbors(coord);
 
-        loop {
-            // If there are no entries next, or if the next entry is not
-            // a digit, we stop accumulating.
-            let Some((next_coord, Value::Digit(next_digit))) = iter.peek() else {
-                break;
-            };
+        // If there are no entries next, or if the next entry is not a digit, we
+        // stop accumulating.
+        while let Some((next_coord, Value::Digit(next_digit))) = iter.peek() {
             // If the next digit is not in the same row as the previous
             // digits, then they are part of different numbers, so we
             // also stop accumulating.
@@ -64,10 +61,7 @@ fn part2(input: &str) -> u32 {
     let mut map = HashMap::<Coord, (/* count */ usize, /* ratio */ u32)>::new();
 
     let mut iter = grid.entries().peekable();
-    loop {
-        let Some((coord @ (row, _), value)) = iter.next() else {
-            break;
-        };
+    while let Some((coord @ (row, _), value)) = iter.next() {
         let Value::Digit(digit) = value else {
             continue;
         };
@@ -77,10 +71,7 @@ fn part2(input: &str) -> u32 {
         // Keep track of all cogs adjacent to the number being accumulated.
         let mut cogs_seen: HashSet<_> = grid.cog_neighbors(coord).collect();
 
-        loop {
-            let Some((next_coord, Value::Digit(next_digit))) = iter.peek() else {
-                break;
-            };
+        while let Some((next_coord, Value::Digit(next_digit))) = iter.peek() {
             if row != next_coord.0 {
                 break;
             }
@@ -96,7 +87,11 @@ fn part2(input: &str) -> u32 {
         for cog_coord in cogs_seen {
             let (count, ratio) = map.entry(cog_coord).or_insert((0, 1));
             *count += 1;
-            *ratio *= number;
+            // Only the ratios of two numbers are summed, and multiplying more
+            // of them could overflow.
+            if *count <= 2 {
+                *ratio *= number;
+            }
         }
     }
 
@@ -106,11 +101,55 @@ fn part2(input: &str) -> u32 {
         .sum()
 }
 
-fn main() {
-    println!("part1: {}", part1(INPUT));
-    println!("part2: {}", part2(INPUT));
+/// Generates a `size` by `size` grid of numbers of up to 3 digits, symbols and
+/// empty cells.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    static SYMBOLS: &[char] = &['*', '#', '+', '$', '/', '@', '=', '%', '-', '&'];
+
+    let mut input = String::new();
+    for _ in 0..size {
+        let mut row = String::new();
+        while row.len() < size {
+            match rng.range(0..12) {
+                0 | 1 => {
+                    let number = rng.range(1..1000).to_string();
+                    if row.len() + number.len() <= size {
+                        row.push_str(&number);
+                        // Keeps the number apart from the next one.
+                        if row.len() < size {
+                            row.push('.');
+                        }
+                    }
+                }
+                2 => row.push(*rng.choose(SYMBOLS)),
+                _ => row.push('.'),
+            }
+        }
+        input.push_str(&row);
+        input.push('\n');
+    }
+    input
 }
 
+pub static SOLUTION: aoc::Solution = aoc::Solution::new(
+    INPUT,
+    |input, _| part1(input).to_string(),
+    |input, _| part2(input).to_string(),
+)
+.with_parse(|input| {
+    black_box(input.parse::<Grid>().expect("valid grid"));
+})
+.with_generator(generate)
+.with_invariants(&[aoc::Invariant::new(
+    "all rows have the same width",
+    rows_have_same_width,
+)])
+.with_answers([Some("546563"), Some("91031374")])
+.with_examples(&[aoc::Example {
+    input: include_str!("example1.txt"),
+    answers: [Some("4361"), Some("467835")],
+}]);
+
 type Coord = (usize, usize);
 
 #[derive(Debug)]
@@ -134,7 +173,7 @@ impl TryFrom<char> for Value {
 
 #[derive(Debug)]
 struct Grid {
-    grid: Vec<Value>,
+    cells: Vec<Value>,
     width: usize,
     height: usize,
 }
@@ -143,7 +182,7 @@ impl Index<Coord> for Grid {
     type Output = Value;
 
     fn index(&self, (row, col): Coord) -> &Self::Output {
-        &self.grid[row * self.width + col]
+        &self.cells[row * self.width + col]
     }
 }
 
@@ -154,7 +193,7 @@ impl FromStr for Grid {
         let width = input.lines().next().expect("at least one line").len();
         let mut height = 0;
 
-        let grid = input
+        let cells = input
             .lines()
             .flat_map(|line| {
                 height += 1;
@@ -164,17 +203,34 @@ impl FromStr for Grid {
             .collect();
 
         Ok(Grid {
-            grid,
+            cells,
             width,
             height,
         })
     }
 }
 
+/// The grid takes its width from the first row, and uses it to index all
+/// the others.
+fn rows_have_same_width(input: &str) -> Result<(), String> {
+    let mut lines = input.lines();
+    let width = lines.next().ok_or("input is empty")?.len();
+    for (i, line) in lines.enumerate() {
+        if line.len() != width {
+            return Err(format!(
+                "row {} has width {}, but the first one has width {width}",
+                i + 2,
+                line.len()
+            ));
+        }
+    }
+    Ok(())
+}
+
 impl Grid {
     fn entries(&self) -> impl Iterator<Item = (Coord, &Value)> {
         let w = self.width;
-        self.grid
+        self.cells
             .iter()
             .enumerate()
             .map(move |(i, v)| ((i / w, i % w), v))
@@ -231,47 +287,39 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(
-            part1(
-                "\
-467..114..
-...*......
-..35..633.
-......#...
-617*......
-.....+.58.
-..592.....
-......755.
-...$.*....
-.664.598.."
-            ),
-            4361
-        );
+        aoc::check_examples(&SOLUTION, aoc::Part::One);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(
-            part2(
-                "\
-467..114..
-...*......
-..35..633.
-......#...
-617*......
-.....+.58.
-..592.....
-......755.
-...$.*....
-.664.598.."
-            ),
-            467835
-        );
+        aoc::check_examples(&SOLUTION, aoc::Part::Two);
+    }
+
+    #[test]
+    fn test_cog_with_many_numbers() {
+        // The product of the four numbers would overflow, but the cog isn't a
+        // gear anyway; only the one on the right is.
+        let input = "\
+999.999....
+...*.....12
+999.999.*..
+.......3...";
+        assert_eq!(part2(input), 36);
+    }
+
+    #[test]
+    fn test_invariants() {
+        assert_eq!(rows_have_same_width("..\n.*\n.."), Ok(()));
+        assert!(rows_have_same_width("..\n.*.\n..").is_err());
+    }
+
+    #[test]
+    fn test_generate() {
+        aoc::check_generate(&SOLUTION);
     }
 
     #[test]
     fn test_answers() {
-        assert_eq!(part1(INPUT), 546563);
-        assert_eq!(part2(INPUT), 91031374);
+        aoc::check_answers(&SOLUTION);
     }
 }
@@ -0,0 +1,334 @@
+use std::{
+    collections::HashSet,
+    fmt::{self, Write as _},
+    hint::black_box,
+};
+
+use aoc::Rng;
+
+static INPUT: &str = include_str!("input.txt");
+
+/// The largest number on a card that fits in the mask of [`card_wins`].
+const MAX_NUMBER: usize = 127;
+
+/// The numbers on the cards are all below 128 (see [`numbers_fit_in_mask`]),
+/// so the winning numbers fit in a bit mask.
+fn card_wins(card: &Card) -> usize {
+    let bit = |n: usize| {
+        1_u128
+            .checked_shl(u32::try_from(n).unwrap())
+            .expect("the numbers on the cards are below 128")
+    };
+    let winning = card.winning.iter().fold(0, |mask, &n| mask | bit(n));
+    card.have.iter().filter(|&&n| winning & bit(n) != 0).count()
+}
+
+/// [`card_wins`] with a hash set of the winning numbers, as it was before
+/// the bit mask.
+fn card_wins_hash_set(card: &Card) -> usize {
+    let winning: HashSet<usize> = card.winning.iter().copied().collect();
+    card.have.iter().filter(|n| winning.contains(n)).count()
+}
+
+fn points(wins: usize) -> usize {
+    if 0 < wins {
+        2_usize.pow(u32::try_from(wins - 1).unwrap())
+    } else {
+        0
+    }
+}
+
+fn part1(input: &str) -> usize {
+    let table = parsers::parse_table(input).unwrap();
+    table.iter().map(|card| points(card_wins(card))).sum()
+}
+
+/// `part1` with [`card_wins_hash_set`].
+fn part1_hash_set(input: &str) -> usize {
+    let table = parsers::parse_table(input).unwrap();
+    table
+        .iter()
+        .map(|card| points(card_wins_hash_set(card)))
+        .sum()
+}
+
+fn part2(input: &str) -> usize {
+    let table = parsers::parse_table(input).unwrap();
+
+    // Instead of adding the copies won by a card to each of the following
+    // cards, we keep a running count of the copies that still apply to the
+    // current card, and record (at the index of the first card they no longer
+    // apply to) when they expire. This makes the cascade linear in the number
+    // of cards, no matter how many copies are won.
+    //
+    // In this case, since the input is sequential and contiguous, we don't have
+    // to use a hash map.
+    let mut expiring = vec![0; table.len() + 1];
+    let mut active = 0;
+    let mut total = 0;
+
+    for card in &table {
+        let card_index = card.number - 1;
+        let wins = card_wins(card);
+
+        // Only elements that appeared *before* the current card may copy it,
+        // so when processing the n-th card, we already know how many copies of
+        // it were created.
+        active -= expiring[card_index];
+        let copies = 1 + active;
+        total += copies;
+
+        // Each of the current card's copies wins one copy of the next `wins`
+        // cards (which can't go past the end of the table).
+        active += copies;
+        expiring[(card_index + 1 + wins).min(table.len())] += copies;
+    }
+
+    total
+}
+
+/// `part2` as it was before the running count: adding the copies won by each
+/// copy of a card to the following cards, one at a time.
+fn part2_naive(input: &str) -> usize {
+    let table = parsers::parse_table(input).unwrap();
+
+    // Map (CardNumber - 1) to the number of copies of the corresponding Card.
+    // Initialized with ones since initially we have a single of each card.
+    let mut copies = vec![1; table.len()];
+
+    for card in &table {
+        let card_index = card.number - 1;
+        let wins = card_wins_hash_set(card);
+
+        for _ in 0..copies[card_index] {
+            let lo = card_index + 1;
+            let hi = lo + wins;
+            for i in lo..hi {
+                if let Some(num) = copies.get_mut(i) {
+                    *num += 1;
+                }
+            }
+        }
+    }
+
+    copies.into_iter().sum()
+}
+
+/// Generates a table of `size` cards, each with 10 winning numbers and 25
+/// numbers we have, laid out as in the real input. Cards win a copy of the
+/// next card less than once on average, since the copies would otherwise grow
+/// exponentially with the size.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let width = size.to_string().len();
+    let mut input = String::new();
+    for number in 1..=size {
+        let mut numbers: Vec<usize> = (1..100).collect();
+        rng.shuffle(&mut numbers);
+        let (winning, others) = numbers.split_at(10);
+        let wins = if rng.one_in(3) { rng.index(4) } else { 0 };
+        let mut have = [&winning[..wins], &others[..25 - wins]].concat();
+        rng.shuffle(&mut have);
+
+        let card = Card {
+            number,
+            winning: winning.to_vec(),
+            have,
+        };
+        let _ = writeln!(input, "{card:width$}");
+    }
+    input
+}
+
+pub static SOLUTION: aoc::Solution = aoc::Solution::new(
+    INPUT,
+    |input, _| part1(input).to_string(),
+    |input, _| part2(input).to_string(),
+)
+.with_parse(|input| {
+    black_box(parsers::parse_table(input).unwrap());
+})
+.with_variants(&[
+    aoc::Variant::new("hash-set", aoc::Part::One, |input, _| {
+        part1_hash_set(input).to_string()
+    }),
+    aoc::Variant::new("naive", aoc::Part::Two, |input, _| {
+        part2_naive(input).to_string()
+    }),
+])
+.with_generator(generate)
+.with_invariants(&[
+    aoc::Invariant::new(
+        "the numbers on the cards are below 128",
+        numbers_fit_in_mask,
+    ),
+    aoc::Invariant::new(
+        "cards are numbered contiguously from 1",
+        cards_numbered_from_one,
+    )
+    .only(aoc::Part::Two),
+])
+.with_answers([Some("21105"), Some("5329815")])
+.with_examples(&[aoc::Example {
+    input: include_str!("example1.txt"),
+    answers: [Some("13"), Some("30")],
+}]);
+
+#[derive(Debug, PartialEq, Eq)]
+struct Card {
+    number: usize,
+    winning: Vec<usize>,
+    have: Vec<usize>,
+}
+
+/// Prints the card back as in the input, with the numbers right-aligned in
+/// columns of two. The card number is right-aligned to the width, if given
+/// (e.g. `{card:3}`), since that depends on the rest of the table.
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = f.width().unwrap_or(0);
+        write!(f, "Card {:>width$}:", self.number)?;
+        for n in &self.winning {
+            write!(f, " {n:>2}")?;
+        }
+        f.write_str(" |")?;
+        for n in &self.have {
+            write!(f, " {n:>2}")?;
+        }
+        Ok(())
+    }
+}
+
+/// [`card_wins`] keeps the winning numbers in a 128-bit mask.
+fn numbers_fit_in_mask(input: &str) -> Result<(), String> {
+    let table = parsers::parse_table(input).map_err(|e| e.to_string())?;
+    for card in &table {
+        let numbers = card.winning.iter().chain(&card.have);
+        if let Some(n) = numbers.copied().find(|&n| n > MAX_NUMBER) {
+            return Err(format!("card {} has the number {n}", card.number));
+        }
+    }
+    Ok(())
+}
+
+/// `part2` uses the card number to index the copies of each card.
+fn cards_numbered_from_one(input: &str) -> Result<(), String> {
+    let table = parsers::parse_table(input).map_err(|e| e.to_string())?;
+    for (i, card) in table.iter().enumerate() {
+        if card.number != i + 1 {
+            return Err(format!("card {} is numbered {}", i + 1, card.number));
+        }
+    }
+    Ok(())
+}
+
+mod parsers {
+    use nom::{
+        bytes::complete::tag,
+        character::complete::{digit1, line_ending, multispace0, space0},
+        combinator::{eof, map, map_res, recognize},
+        error::Error,
+        multi::{many1, separated_list0, separated_list1},
+        sequence::{delimited, tuple},
+        Err, IResult,
+    };
+
+    use super::*;
+
+    pub fn parse_table(input: &str) -> Result<Vec<Card>, Err<Error<&str>>> {
+        let (_, (table, _, _)) = tuple((
+            //
+            separated_list0(line_ending, parse_card),
+            multispace0,
+            eof,
+        ))(input)?;
+        Ok(table)
+    }
+
+    fn parse_card(input: &str) -> IResult<&str, Card> {
+        map(
+            tuple((
+                tag("Card"),
+                space0,
+                number,
+                tag(":"),
+                number_list,
+                tag("|"),
+                number_list,
+            )),
+            |(_, _, number, _, winning, _, have)| Card {
+                number,
+                winning,
+                have,
+            },
+        )(input)
+    }
+
+    fn number_list(input: &str) -> IResult<&str, Vec<usize>> {
+        delimited(space0, separated_list1(many1(tag(" ")), number), space0)(input)
+    }
+
+    fn number(input: &str) -> IResult<&str, usize> {
+        map_res(recognize(digit1), str::parse)(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        aoc::check_examples(&SOLUTION, aoc::Part::One);
+    }
+
+    #[test]
+    fn test_part2() {
+        aoc::check_examples(&SOLUTION, aoc::Part::Two);
+    }
+
+    #[test]
+    fn test_invariants() {
+        assert_eq!(numbers_fit_in_mask("Card 1: 1 127 | 0"), Ok(()));
+        assert_eq!(
+            numbers_fit_in_mask("Card 1: 1 | 128"),
+            Err("card 1 has the number 128".to_owned())
+        );
+        assert_eq!(
+            cards_numbered_from_one("Card 1: 1 | 2\nCard 2: 3 | 4"),
+            Ok(())
+        );
+        assert_eq!(
+            cards_numbered_from_one("Card 1: 1 | 2\nCard 3: 3 | 4"),
+            Err("card 2 is numbered 3".to_owned())
+        );
+    }
+
+    fn card() -> impl Strategy<Value = Card> {
+        let numbers = || prop::collection::vec(0..1000_usize, 1..30);
+        (0..1000_usize, numbers(), numbers()).prop_map(|(number, winning, have)| Card {
+            number,
+            winning,
+            have,
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_print_parse(table in prop::collection::vec(card(), 0..5), width in 0..5_usize) {
+            let printed: Vec<_> = table.iter().map(|card| format!("{card:width$}")).collect();
+            prop_assert_eq!(parsers::parse_table(&printed.join("\n")).unwrap(), table);
+        }
+    }
+
+    #[test]
+    fn test_generate() {
+        aoc::check_generate(&SOLUTION);
+    }
+
+    #[test]
+    fn test_answers() {
+        aoc::check_answers(&SOLUTION);
+    }
+}
@@ -0,0 +1,434 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt::{self, Write as _},
+    hint::black_box,
+};
+
+use aoc::{Context, Rng};
+
+static INPUT: &str = include_str!("input.txt");
+
+fn convert<'a>(cs: &'a Conversions<'a>, from: &str, to: &str, value: usize) -> usize {
+    if from == to {
+        return value;
+    }
+    let conversion = &cs[from];
+    let mapped_value = conversion
+        .range_sets
+        .iter()
+        .find_map(|set| set.convert(value))
+        // If there is no conversion, the value is mapped to itself.
+        .unwrap_or(value);
+    convert(cs, conversion.to, to, mapped_value)
+}
+
+/// Converts whole ranges of values at once, splitting them where the range
+/// sets of the conversions start and end.
+fn convert_ranges<'a>(
+    cs: &'a Conversions<'a>,
+    from: &str,
+    to: &str,
+    ranges: Vec<Range>,
+) -> Vec<Range> {
+    if from == to {
+        return ranges;
+    }
+    let conversion = &cs[from];
+    let mut ranges = ranges;
+    let mut mapped = Vec::new();
+    while let Some(range) = ranges.pop() {
+        if range.is_empty() {
+            continue;
+        }
+        let overlapping = conversion
+            .range_sets
+            .iter()
+            .find(|set| set.src.start < range.end && range.start < set.src.end);
+        let Some(set) = overlapping else {
+            // If there is no conversion, the values are mapped to themselves.
+            mapped.push(range);
+            continue;
+        };
+        let start = range.start.max(set.src.start);
+        let end = range.end.min(set.src.end);
+        mapped.push(set.dst.start + (start - set.src.start)..set.dst.start + (end - set.src.start));
+        // What's left on either side may be in other range sets.
+        ranges.push(range.start..start);
+        ranges.push(end..range.end);
+    }
+    convert_ranges(cs, conversion.to, to, mapped)
+}
+
+fn part1(input: &str) -> usize {
+    let almanac = parsers::parse_almanac(input).unwrap();
+
+    almanac
+        .seeds
+        .into_iter()
+        .map(|seed| convert(&almanac.conversions, "seed", "location", seed))
+        .min()
+        .unwrap()
+}
+
+fn pairs<T, I>(iter: impl IntoIterator<Item = T, IntoIter = I>) -> impl Iterator<Item = (T, T)>
+where
+    I: Iterator<Item = T>,
+{
+    let mut iter = iter.into_iter();
+    std::iter::from_fn(move || {
+        let a = iter.next()?;
+        let b = iter.next().expect("elements pairwise");
+        Some((a, b))
+    })
+}
+
+fn part2(input: &str) -> usize {
+    let almanac = parsers::parse_almanac(input).unwrap();
+
+    let seeds = pairs(almanac.seeds)
+        .map(|(lo, len)| lo..(lo + len))
+        .collect();
+    convert_ranges(&almanac.conversions, "seed", "location", seeds)
+        .into_iter()
+        .map(|range| range.start)
+        .min()
+        .unwrap()
+}
+
+/// How many seeds `part2_brute_force` tries between checkpoints.
+const CHUNK: usize = 1 << 24;
+
+/// `part2` trying every seed, which takes minutes.
+fn part2_brute_force(input: &str, ctx: &Context) -> usize {
+    let almanac = parsers::parse_almanac(input).unwrap();
+
+    // Tries every seed, so the search is cut into chunks to resume it if
+    // it's interrupted.
+    let chunks: Vec<Range> = pairs(almanac.seeds)
+        .flat_map(|(lo, len)| {
+            let end = lo + len;
+            (lo..end)
+                .step_by(CHUNK)
+                .map(move |start| start..end.min(start + CHUNK))
+        })
+        .collect();
+    let solve = |i: usize| {
+        chunks[i]
+            .clone()
+            .map(|seed| convert(&almanac.conversions, "seed", "location", seed))
+            .min()
+    };
+    ctx.checkpoint
+        .search(input, chunks.len(), &ctx.progress, solve, usize::min)
+        .unwrap()
+}
+
+/// Generates an almanac with `size` seed ranges, and up to `size` range sets
+/// in each map. The values are within `1000 * size` of either 0 or 2^32 (the
+/// top of the real inputs' values), and the seed ranges span up to half of
+/// that, so `part2_brute_force` still finishes on small sizes.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    static CATEGORIES: &[&str] = &[
+        "seed",
+        "soil",
+        "fertilizer",
+        "water",
+        "light",
+        "temperature",
+        "humidity",
+        "location",
+    ];
+    const TOP: u64 = 1 << 32;
+    let max = (1000 * size as u64).min(TOP / 2);
+    // Some values are near the top, where the ranges may end right at it.
+    let value = |rng: &mut Rng| {
+        let n = rng.range(0..max);
+        if rng.one_in(4) {
+            TOP - 1 - n
+        } else {
+            n
+        }
+    };
+
+    let seeds: Vec<String> = (0..size)
+        .flat_map(|_| {
+            let start = value(rng);
+            [start, rng.range(1..max / 2 + 1).min(TOP - start)]
+        })
+        .map(|n| n.to_string())
+        .collect();
+    let mut input = format!("seeds: {}\n", seeds.join(" "));
+    for pair in CATEGORIES.windows(2) {
+        let _ = write!(input, "\n{}-to-{} map:\n", pair[0], pair[1]);
+        // The sources don't overlap, as in the real input: every set takes
+        // two of the bounds.
+        let sets = rng.index(size) + 1;
+        let mut bounds = BTreeSet::new();
+        while bounds.len() < 2 * sets {
+            bounds.insert(value(rng));
+        }
+        let bounds: Vec<_> = bounds.into_iter().collect();
+        let mut lines: Vec<String> = bounds
+            .chunks(2)
+            .map(|src| {
+                let len = src[1] - src[0];
+                format!("{} {} {len}", value(rng).min(TOP - len), src[0])
+            })
+            .collect();
+        rng.shuffle(&mut lines);
+        for line in lines {
+            input.push_str(&line);
+            input.push('\n');
+        }
+    }
+    input
+}
+
+pub static SOLUTION: aoc::Solution = aoc::Solution::new(
+    INPUT,
+    |input, _| part1(input).to_string(),
+    |input, _| part2(input).to_string(),
+)
+.with_parse(|input| {
+    black_box(parsers::parse_almanac(input).unwrap());
+})
+.with_variants(&[aoc::Variant::new(
+    "brute-force",
+    aoc::Part::Two,
+    |input, ctx| part2_brute_force(input, ctx).to_string(),
+)])
+.with_generator(generate)
+.with_invariants(&[
+    aoc::Invariant::new("seeds come in (start, length) pairs", seeds_are_pairs)
+        .only(aoc::Part::Two),
+])
+.with_answers([Some("825516882"), Some("136096660")])
+.with_examples(&[aoc::Example {
+    input: include_str!("example1.txt"),
+    answers: [Some("35"), Some("46")],
+}]);
+
+type Range = std::ops::Range<usize>;
+
+#[derive(Debug, PartialEq, Eq)]
+struct Almanac<'a> {
+    seeds: Vec<usize>,
+    conversions: Conversions<'a>,
+}
+
+/// Prints the almanac back as in the input. The almanac doesn't keep the
+/// order of the maps, so they're printed by the category they map from.
+impl fmt::Display for Almanac<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("seeds:")?;
+        for seed in &self.seeds {
+            write!(f, " {seed}")?;
+        }
+        writeln!(f)?;
+        let mut conversions: Vec<_> = self.conversions.values().collect();
+        conversions.sort_by_key(|conversion| conversion.from);
+        for conversion in conversions {
+            write!(f, "\n{}-to-{} map:\n", conversion.from, conversion.to)?;
+            for set in &conversion.range_sets {
+                let len = set.src.len();
+                writeln!(f, "{} {} {len}", set.dst.start, set.src.start)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `part2` reads the seeds as (start, length) pairs.
+fn seeds_are_pairs(input: &str) -> Result<(), String> {
+    let almanac = parsers::parse_almanac(input).map_err(|e| e.to_string())?;
+    let count = almanac.seeds.len();
+    if count % 2 == 0 {
+        Ok(())
+    } else {
+        Err(format!("found an odd number of seeds ({count})"))
+    }
+}
+
+type Conversions<'a> = HashMap</* from */ &'a str, Conversion<'a>>;
+
+#[derive(Debug, PartialEq, Eq)]
+struct Conversion<'a> {
+    from: &'a str,
+    to: &'a str,
+    range_sets: Vec<RangeSet>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct RangeSet {
+    src: Range,
+    dst: Range,
+}
+
+impl RangeSet {
+    fn convert(&self, value: usize) -> Option<usize> {
+        self.src.contains(&value).then(|| {
+            let offset = value - self.src.start;
+            self.dst.start + offset
+        })
+    }
+}
+
+mod parsers {
+    use nom::{
+        bytes::complete::tag,
+        character::complete::{alpha1, digit1, multispace0, newline, space1},
+        combinator::{eof, map_res, recognize},
+        error::Error,
+        multi::{count, separated_list1},
+        sequence::tuple,
+        Err, IResult,
+    };
+
+    use super::*;
+
+    // seeds: 79 14 55 13
+    //
+    // seed-to-soil map:
+    // 50 98 2
+    // 52 50 48
+    //
+    // soil-to-fertilizer map:
+    // 0 15 37
+    // 37 52 2
+    // 39 0 15
+    pub fn parse_almanac(i: &str) -> Result<Almanac<'_>, Err<Error<&str>>> {
+        let sep = |i| count(newline, 2)(i);
+
+        let (i, _) = tag("seeds: ")(i)?;
+        let (i, seeds) = number_list(i)?;
+        let (i, _) = sep(i)?;
+        let (i, conversions) = separated_list1(sep, parse_conversion)(i)?;
+        let (i, _) = multispace0(i)?;
+        eof(i)?;
+
+        Ok(Almanac {
+            seeds,
+            conversions: conversions.into_iter().map(|c| (c.from, c)).collect(),
+        })
+    }
+
+    // seed-to-soil map:
+    // 50 98 2
+    // 52 50 48
+    fn parse_conversion(i: &str) -> IResult<&str, Conversion<'_>> {
+        let (i, from) = alpha1(i)?;
+        let (i, _) = tag("-to-")(i)?;
+        let (i, to) = alpha1(i)?;
+        let (i, _) = tag(" map:")(i)?;
+        let (i, _) = newline(i)?;
+        let (i, range_sets) = separated_list1(newline, parse_range_set)(i)?;
+
+        Ok((
+            i,
+            Conversion {
+                from,
+                to,
+                range_sets,
+            },
+        ))
+    }
+
+    // 50 98 2
+    fn parse_range_set(i: &str) -> IResult<&str, RangeSet> {
+        let (i, (dst0, _, src0, _, len)) = tuple((
+            //
+            number, space1, number, space1, number,
+        ))(i)?;
+        Ok((
+            i,
+            RangeSet {
+                src: src0..(src0 + len),
+                dst: dst0..(dst0 + len),
+            },
+        ))
+    }
+
+    fn number_list(i: &str) -> IResult<&str, Vec<usize>> {
+        separated_list1(space1, number)(i)
+    }
+
+    fn number(i: &str) -> IResult<&str, usize> {
+        map_res(recognize(digit1), str::parse)(i)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        aoc::check_examples(&SOLUTION, aoc::Part::One);
+    }
+
+    #[test]
+    fn test_part2() {
+        aoc::check_examples(&SOLUTION, aoc::Part::Two);
+    }
+
+    #[test]
+    fn test_invariants() {
+        let almanac = |seeds| format!("seeds: {seeds}\n\nseed-to-soil map:\n50 98 2\n");
+        assert_eq!(seeds_are_pairs(&almanac("79 14 55 13")), Ok(()));
+        assert!(seeds_are_pairs(&almanac("79 14 55")).is_err());
+    }
+
+    #[test]
+    fn test_generate() {
+        aoc::check_generate(&SOLUTION);
+    }
+
+    /// The seeds, and the maps from each category to the other category and
+    /// the `(dst, src, len)` of the range sets. The almanac borrows the names,
+    /// so it's built from these in the test.
+    type Parts = (
+        Vec<usize>,
+        BTreeMap<String, (String, Vec<(usize, usize, usize)>)>,
+    );
+
+    fn parts() -> impl Strategy<Value = Parts> {
+        let number = || 0..1_usize << 32;
+        let range_sets = prop::collection::vec((number(), number(), number()), 1..5);
+        let conversions =
+            prop::collection::btree_map("[a-z]{1,12}", ("[a-z]{1,12}", range_sets), 1..8);
+        (prop::collection::vec(number(), 1..10), conversions)
+    }
+
+    proptest! {
+        #[test]
+        fn test_print_parse((seeds, conversions) in parts()) {
+            let almanac = Almanac {
+                seeds,
+                conversions: conversions
+                    .iter()
+                    .map(|(from, (to, range_sets))| {
+                        let range_sets = range_sets
+                            .iter()
+                            .map(|&(dst, src, len)| RangeSet {
+                                src: src..src + len,
+                                dst: dst..dst + len,
+                            })
+                            .collect();
+                        (from.as_str(), Conversion { from, to, range_sets })
+                    })
+                    .collect(),
+            };
+            let printed = almanac.to_string();
+            prop_assert_eq!(parsers::parse_almanac(&printed).unwrap(), almanac);
+        }
+    }
+
+    #[test]
+    fn test_answers() {
+        aoc::check_answers(&SOLUTION);
+    }
+}
@@ -0,0 +1,263 @@
+//! Single pass scanner for instructions hidden in corrupted memory.
+//!
+//! An instruction is a name immediately followed by a parenthesized, comma
+//! separated list of exactly `arity` numbers of 1 to 3 digits, such as
+//! `mul(44,46)` or `do()`. Everything else is noise and is skipped, though
+//! the lexer can also report near misses (see [`NearMiss`]).
+
+use std::marker::PhantomData;
+
+/// The most arguments an instruction may take.
+pub const MAX_ARITY: usize = 4;
+
+/// A set of instructions the lexer recognizes, usually a fieldless enum.
+pub trait Op: Copy + 'static {
+    /// The name and arity of every instruction. When several instructions
+    /// match at the same offset, the first one wins.
+    const SPECS: &'static [(Self, &'static str, usize)];
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token<O> {
+    pub op: O,
+    /// Byte offset of the start of the instruction name.
+    pub offset: usize,
+    /// Length in bytes, up to and including the closing parenthesis.
+    pub len: usize,
+    args: [u32; MAX_ARITY],
+    arity: usize,
+}
+
+impl<O> Token<O> {
+    pub fn args(&self) -> &[u32] {
+        &self.args[..self.arity]
+    }
+}
+
+/// A known instruction name immediately followed by a bracket that doesn't
+/// make up a valid instruction, such as `mul[3,7]` or `mul(32,64]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NearMiss<O> {
+    pub op: O,
+    pub offset: usize,
+    /// Length in bytes, up to the last byte that could be part of the
+    /// argument list (a closing bracket included).
+    pub len: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lexeme<O> {
+    Token(Token<O>),
+    NearMiss(NearMiss<O>),
+}
+
+pub struct Lexer<'a, O> {
+    src: &'a [u8],
+    pos: usize,
+    _op: PhantomData<O>,
+}
+
+impl<'a, O: Op> Lexer<'a, O> {
+    pub fn new(src: &'a str) -> Self {
+        assert!(
+            O::SPECS.iter().all(|&(_, _, arity)| arity <= MAX_ARITY),
+            "instructions take at most {MAX_ARITY} arguments"
+        );
+        Lexer {
+            src: src.as_bytes(),
+            pos: 0,
+            _op: PhantomData,
+        }
+    }
+
+    /// Tries to read the given instruction starting at `start`, returning its
+    /// arguments and the offset right after it.
+    fn instruction_at(
+        &self,
+        start: usize,
+        name: &str,
+        arity: usize,
+    ) -> Option<([u32; MAX_ARITY], usize)> {
+        let mut cursor = Cursor {
+            src: self.src,
+            pos: start,
+        };
+        cursor.eat(name.as_bytes())?;
+        cursor.eat(b"(")?;
+        let mut args = [0; MAX_ARITY];
+        for (i, arg) in args.iter_mut().take(arity).enumerate() {
+            if i > 0 {
+                cursor.eat(b",")?;
+            }
+            *arg = cursor.number()?;
+        }
+        cursor.eat(b")")?;
+        Some((args, cursor.pos))
+    }
+
+    /// Checks for a near miss of the given instruction at `start`.
+    fn near_miss_at(&self, start: usize, op: O, name: &str) -> Option<NearMiss<O>> {
+        let mut cursor = Cursor {
+            src: self.src,
+            pos: start,
+        };
+        cursor.eat(name.as_bytes())?;
+        cursor.eat(b"(").or_else(|| cursor.eat(b"["))?;
+        let rest = &self.src[cursor.pos..];
+        let mut end = cursor.pos
+            + rest
+                .iter()
+                .take_while(|&&b| b.is_ascii_digit() || b == b',')
+                .count();
+        if matches!(self.src.get(end), Some(b')' | b']')) {
+            end += 1;
+        }
+        Some(NearMiss {
+            op,
+            offset: start,
+            len: end - start,
+        })
+    }
+
+    fn next_lexeme(&mut self) -> Option<Lexeme<O>> {
+        while self.pos < self.src.len() {
+            let start = self.pos;
+            let mut near_miss = None;
+            for &(op, name, arity) in O::SPECS {
+                if let Some((args, end)) = self.instruction_at(start, name, arity) {
+                    self.pos = end;
+                    return Some(Lexeme::Token(Token {
+                        op,
+                        offset: start,
+                        len: end - start,
+                        args,
+                        arity,
+                    }));
+                }
+                near_miss = near_miss.or_else(|| self.near_miss_at(start, op, name));
+            }
+            // Near misses don't consume their bytes, so they never hide a valid
+            // instruction.
+            self.pos += 1;
+            if let Some(near_miss) = near_miss {
+                return Some(Lexeme::NearMiss(near_miss));
+            }
+        }
+        None
+    }
+
+    /// Like iterating over the tokens, but also yields the near misses.
+    pub fn lexemes(mut self) -> impl Iterator<Item = Lexeme<O>> + 'a {
+        std::iter::from_fn(move || self.next_lexeme())
+    }
+}
+
+impl<O: Op> Iterator for Lexer<'_, O> {
+    type Item = Token<O>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Lexeme::Token(token) = self.next_lexeme()? {
+                return Some(token);
+            }
+        }
+    }
+}
+
+struct Cursor<'a> {
+    src: &'a [u8],
+    pos: usize,
+}
+
+impl Cursor<'_> {
+    fn eat(&mut self, expected: &[u8]) -> Option<()> {
+        let rest = &self.src[self.pos..];
+        rest.starts_with(expected)
+            .then(|| self.pos += expected.len())
+    }
+
+    fn number(&mut self) -> Option<u32> {
+        let rest = &self.src[self.pos..];
+        let len = rest
+            .iter()
+            .take(3)
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        if len == 0 {
+            return None;
+        }
+        self.pos += len;
+        Some(
+            rest[..len]
+                .iter()
+                .fold(0, |n, b| n * 10 + u32::from(b - b'0')),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum TestOp {
+        Add,
+        Neg,
+        Nop,
+    }
+
+    impl Op for TestOp {
+        const SPECS: &'static [(Self, &'static str, usize)] = &[
+            (TestOp::Add, "add", 3),
+            (TestOp::Neg, "neg", 1),
+            (TestOp::Nop, "nop", 0),
+        ];
+    }
+
+    fn lex(src: &str) -> Vec<(TestOp, usize, &str, Vec<u32>)> {
+        Lexer::<TestOp>::new(src)
+            .map(|t| (t.op, t.offset, &src[t.offset..][..t.len], t.args().to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn test_tokens() {
+        assert_eq!(
+            lex("xadd(1,22,333)nop()?neg(7)"),
+            [
+                (TestOp::Add, 1, "add(1,22,333)", vec![1, 22, 333]),
+                (TestOp::Nop, 14, "nop()", vec![]),
+                (TestOp::Neg, 20, "neg(7)", vec![7]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_noise() {
+        // Wrong arity, too many digits, spaces and wrong brackets.
+        assert_eq!(lex("add(1,2)neg(1234)nop( )neg[1]add(1,2,3,4)nop(1)"), []);
+        // A broken instruction doesn't hide a valid one inside it.
+        assert_eq!(lex("neg(neg(5)"), [(TestOp::Neg, 4, "neg(5)", vec![5])]);
+    }
+
+    #[test]
+    fn test_near_misses() {
+        let src = "neg[3]add(1,2]nop(neg(5)nopnop(1)";
+        let near_misses: Vec<_> = Lexer::<TestOp>::new(src)
+            .lexemes()
+            .filter_map(|lexeme| match lexeme {
+                Lexeme::NearMiss(n) => Some((n.op, &src[n.offset..][..n.len])),
+                Lexeme::Token(_) => None,
+            })
+            .collect();
+        assert_eq!(
+            near_misses,
+            [
+                (TestOp::Neg, "neg[3]"),
+                (TestOp::Add, "add(1,2]"),
+                (TestOp::Nop, "nop("),
+                (TestOp::Nop, "nop(1)"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,153 @@
+mod lexer;
+mod machine;
+mod render;
+
+use std::hint::black_box;
+
+use aoc::Rng;
+use lexer::Lexer;
+use machine::{Config, Effect, Machine};
+
+pub static INPUT: &str = include_str!("input.txt");
+
+pub static SOLUTION: aoc::Solution = aoc::Solution::new(
+    INPUT,
+    |input, _| part1(input).to_string(),
+    |input, _| part2(input).to_string(),
+)
+.with_parse(|input| {
+    black_box(Lexer::<Op>::new(input).count());
+})
+.with_generator(generate)
+.with_answers([Some("159833790"), Some("89349241")])
+.with_examples(&[
+    aoc::Example {
+        input: include_str!("example1.txt"),
+        answers: [Some("161"), None],
+    },
+    aoc::Example {
+        input: include_str!("example2.txt"),
+        answers: [None, Some("48")],
+    },
+]);
+
+/// Generates `size` pieces of corrupted memory: instructions, near misses
+/// like `mul(4*` or `mul[3,7]`, and noise, over a few lines.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    static NOISE: &[&str] = &[
+        "select()", "from()", "what()", "why()", "who()", "where()", "how()", "when()", "mul",
+        "do", "don't", "'", "%", "&", "$", "#", "@", "!", "^", "~", "[", "]", "{", "}", "<", ">",
+        ",", ";", ":", "?", "+", "-", "*", "/", " ", "(", ")",
+    ];
+
+    let mut input = String::new();
+    for _ in 0..size {
+        let (a, b) = (rng.range(0..1000), rng.range(0..1000));
+        let piece = match rng.range(0..12) {
+            0..=2 => format!("mul({a},{b})"),
+            3 => "do()".to_owned(),
+            4 => "don't()".to_owned(),
+            5 => match rng.range(0..5) {
+                0 => format!("mul({a},{b}]"),
+                1 => format!("mul[{a},{b})"),
+                2 => format!("mul({a}, {b})"),
+                3 => format!("mul({a},{b}"),
+                _ => format!("mul({a}{},{b})", rng.range(0..10)),
+            },
+            _ => (*rng.choose(NOISE)).to_owned(),
+        };
+        input.push_str(&piece);
+        if rng.one_in(100) {
+            input.push('\n');
+        }
+    }
+    input.push('\n');
+    input
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// ANSI escape codes, for the terminal.
+    Ansi,
+    /// A standalone HTML page.
+    Html,
+}
+
+/// Renders the input highlighting what the part 2 interpreter makes of it.
+#[must_use]
+pub fn render(input: &str, format: Format) -> String {
+    let config = Config { conditionals: true };
+    match format {
+        Format::Ansi => render::ansi::<Op>(input, config),
+        Format::Html => render::html::<Op>(input, config),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Mul,
+    Do,
+    Dont,
+}
+
+impl lexer::Op for Op {
+    const SPECS: &'static [(Self, &'static str, usize)] = &[
+        (Op::Mul, "mul", 2),
+        (Op::Do, "do", 0),
+        (Op::Dont, "don't", 0),
+    ];
+}
+
+impl machine::Instruction for Op {
+    fn effect(self, args: &[u32]) -> Effect {
+        match (self, args) {
+            (Op::Mul, &[a, b]) => Effect::Accumulate(i64::from(a) * i64::from(b)),
+            (Op::Do, _) => Effect::Enable,
+            (Op::Dont, _) => Effect::Disable,
+            (Op::Mul, _) => unreachable!("mul takes two arguments"),
+        }
+    }
+}
+
+fn execute(input: &str, config: Config) -> i64 {
+    Machine::new(config).run(Lexer::<Op>::new(input)).acc
+}
+
+fn part1(input: &str) -> i64 {
+    // Part 1 doesn't know about `do()` and `don't()` yet.
+    execute(
+        input,
+        Config {
+            conditionals: false,
+        },
+    )
+}
+
+fn part2(input: &str) -> i64 {
+    execute(input, Config { conditionals: true })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        aoc::check_examples(&SOLUTION, aoc::Part::One);
+    }
+
+    #[test]
+    fn test_part2() {
+        aoc::check_examples(&SOLUTION, aoc::Part::Two);
+    }
+
+    #[test]
+    fn test_generate() {
+        aoc::check_generate(&SOLUTION);
+    }
+
+    #[test]
+    fn test_answers() {
+        aoc::check_answers(&SOLUTION);
+    }
+}
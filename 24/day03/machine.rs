@@ -0,0 +1,114 @@
+//! Interpreter for the instructions found in corrupted memory.
+
+use crate::lexer::{self, Token};
+
+/// What executing an instruction does to the machine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    Enable,
+    Disable,
+    /// Adds the value to the accumulator, if the machine is enabled.
+    Accumulate(i64),
+}
+
+/// An instruction set the machine knows how to execute.
+pub trait Instruction: lexer::Op {
+    fn effect(self, args: &[u32]) -> Effect;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Config {
+    /// Whether `Enable` and `Disable` take effect. If not, the machine stays
+    /// enabled for the whole program.
+    pub conditionals: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Machine {
+    config: Config,
+    pub enabled: bool,
+    pub acc: i64,
+    /// Number of instructions executed so far, including the ones skipped
+    /// while disabled.
+    pub counter: usize,
+}
+
+impl Machine {
+    pub fn new(config: Config) -> Self {
+        Machine {
+            config,
+            enabled: true,
+            acc: 0,
+            counter: 0,
+        }
+    }
+
+    pub fn step<O: Instruction>(&mut self, token: &Token<O>) {
+        self.counter += 1;
+        match token.op.effect(token.args()) {
+            Effect::Enable if self.config.conditionals => self.enabled = true,
+            Effect::Disable if self.config.conditionals => self.enabled = false,
+            Effect::Accumulate(value) if self.enabled => self.acc += value,
+            _ => {}
+        }
+    }
+
+    /// Executes every instruction in the token stream.
+    #[must_use]
+    pub fn run<O: Instruction>(mut self, tokens: impl IntoIterator<Item = Token<O>>) -> Self {
+        for token in tokens {
+            self.step(&token);
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::Lexer;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum TestOp {
+        Add,
+        On,
+        Off,
+    }
+
+    impl lexer::Op for TestOp {
+        const SPECS: &'static [(Self, &'static str, usize)] = &[
+            (TestOp::Add, "add", 1),
+            (TestOp::On, "on", 0),
+            (TestOp::Off, "off", 0),
+        ];
+    }
+
+    impl Instruction for TestOp {
+        fn effect(self, args: &[u32]) -> Effect {
+            match self {
+                TestOp::Add => Effect::Accumulate(args[0].into()),
+                TestOp::On => Effect::Enable,
+                TestOp::Off => Effect::Disable,
+            }
+        }
+    }
+
+    #[test]
+    fn test_run() {
+        let program = "add(1)off()add(10)on()add(100)off()";
+        let run =
+            |conditionals| Machine::new(Config { conditionals }).run(Lexer::<TestOp>::new(program));
+
+        let machine = run(true);
+        assert_eq!(
+            (machine.acc, machine.counter, machine.enabled),
+            (101, 6, false)
+        );
+
+        let machine = run(false);
+        assert_eq!(
+            (machine.acc, machine.counter, machine.enabled),
+            (111, 6, true)
+        );
+    }
+}
@@ -0,0 +1,218 @@
+//! Debug rendering of corrupted memory, highlighting what the interpreter
+//! makes of it.
+
+use std::{fmt::Write as _, ops::Range};
+
+use crate::{
+    lexer::{Lexeme, Lexer},
+    machine::{Config, Effect, Instruction, Machine},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Style {
+    /// Noise.
+    Plain,
+    /// An instruction whose value was accumulated.
+    Counted,
+    /// An instruction whose value was ignored since the machine was disabled.
+    Skipped,
+    /// An instruction that enables or disables the machine.
+    Control,
+    /// A near miss, which the interpreter never sees.
+    Rejected,
+}
+
+impl Style {
+    fn ansi(self) -> &'static str {
+        match self {
+            Style::Plain => "",
+            Style::Counted => "\x1b[1;32m",
+            Style::Skipped => "\x1b[9;33m",
+            Style::Control => "\x1b[1;36m",
+            Style::Rejected => "\x1b[4;31m",
+        }
+    }
+
+    fn class(self) -> &'static str {
+        match self {
+            Style::Plain => "plain",
+            Style::Counted => "counted",
+            Style::Skipped => "skipped",
+            Style::Control => "control",
+            Style::Rejected => "rejected",
+        }
+    }
+}
+
+/// How a run of bytes is rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Class {
+    style: Style,
+    /// Whether the bytes are in a region where the machine is disabled.
+    disabled: bool,
+}
+
+/// Classifies every byte of the input, running it through a machine with the
+/// given configuration. Returns runs of bytes with the same class.
+fn classify<O: Instruction>(input: &str, config: Config) -> Vec<(Range<usize>, Class)> {
+    let mut styles = vec![Style::Plain; input.len()];
+    let mut disabled = vec![false; input.len()];
+
+    let mut machine = Machine::new(config);
+    // Where the machine was last disabled, if it still is.
+    let mut disabled_since = None;
+    let mut mark = |range: Range<usize>, style| styles[range].fill(style);
+
+    for lexeme in Lexer::<O>::new(input).lexemes() {
+        let token = match lexeme {
+            Lexeme::NearMiss(near_miss) => {
+                let range = near_miss.offset..near_miss.offset + near_miss.len;
+                mark(range, Style::Rejected);
+                continue;
+            }
+            Lexeme::Token(token) => token,
+        };
+
+        let range = token.offset..token.offset + token.len;
+        let style = match token.op.effect(token.args()) {
+            Effect::Enable | Effect::Disable => Style::Control,
+            Effect::Accumulate(_) if machine.enabled => Style::Counted,
+            Effect::Accumulate(_) => Style::Skipped,
+        };
+        mark(range, style);
+
+        machine.step(&token);
+        match (disabled_since, machine.enabled) {
+            (None, false) => disabled_since = Some(token.offset + token.len),
+            (Some(start), true) => {
+                disabled[start..token.offset].fill(true);
+                disabled_since = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(start) = disabled_since {
+        disabled[start..].fill(true);
+    }
+
+    let mut runs: Vec<(Range<usize>, Class)> = Vec::new();
+    for (i, (&style, &disabled)) in styles.iter().zip(&disabled).enumerate() {
+        let class = Class { style, disabled };
+        match runs.last_mut() {
+            Some((range, last)) if *last == class => range.end = i + 1,
+            _ => runs.push((i..i + 1, class)),
+        }
+    }
+    runs
+}
+
+/// Renders the input with ANSI escape codes, for the terminal.
+pub fn ansi<O: Instruction>(input: &str, config: Config) -> String {
+    const RESET: &str = "\x1b[0m";
+    const DIM: &str = "\x1b[2m";
+
+    let mut out = String::new();
+    for (range, class) in classify::<O>(input, config) {
+        if class.disabled {
+            out.push_str(DIM);
+        }
+        out.push_str(class.style.ansi());
+        out.push_str(&input[range]);
+        if class.disabled || class.style != Style::Plain {
+            out.push_str(RESET);
+        }
+    }
+    let _ = write!(
+        out,
+        "\n\n{}counted{RESET} {}skipped{RESET} {}do/don't{RESET} {}rejected{RESET} {DIM}disabled region{RESET}\n",
+        Style::Counted.ansi(),
+        Style::Skipped.ansi(),
+        Style::Control.ansi(),
+        Style::Rejected.ansi(),
+    );
+    out
+}
+
+/// Renders the input as a standalone HTML page.
+pub fn html<O: Instruction>(input: &str, config: Config) -> String {
+    let mut out = String::from(
+        "\
+<!DOCTYPE html>
+<meta charset=\"utf-8\">
+<title>Corrupted memory</title>
+<style>
+  pre { white-space: pre-wrap; word-break: break-all; }
+  .disabled { background: #ddd; color: #888; }
+  .counted { color: #080; font-weight: bold; }
+  .skipped { color: #a60; text-decoration: line-through; }
+  .control { color: #08a; font-weight: bold; }
+  .rejected { color: #c00; text-decoration: underline wavy; }
+</style>
+<p>
+  <span class=\"counted\">counted</span>
+  <span class=\"skipped\">skipped</span>
+  <span class=\"control\">do/don't</span>
+  <span class=\"rejected\">rejected</span>
+  <span class=\"disabled\">disabled region</span>
+</p>
+<pre>",
+    );
+    for (range, class) in classify::<O>(input, config) {
+        let disabled = if class.disabled { " disabled" } else { "" };
+        let _ = write!(out, "<span class=\"{}{disabled}\">", class.style.class());
+        for c in input[range].chars() {
+            match c {
+                '&' => out.push_str("&amp;"),
+                '<' => out.push_str("&lt;"),
+                '>' => out.push_str("&gt;"),
+                c => out.push(c),
+            }
+        }
+        out.push_str("</span>");
+    }
+    out.push_str("</pre>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Op;
+
+    #[test]
+    fn test_classify() {
+        let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+        let runs: Vec<_> = classify::<Op>(input, Config { conditionals: true })
+            .into_iter()
+            .filter(|(_, class)| class.style != Style::Plain || class.disabled)
+            .map(|(range, class)| (&input[range], class.style, class.disabled))
+            .collect();
+        assert_eq!(
+            runs,
+            [
+                ("mul(2,4)", Style::Counted, false),
+                ("mul[3,7]", Style::Rejected, false),
+                ("don't()", Style::Control, false),
+                ("_", Style::Plain, true),
+                ("mul(5,5)", Style::Skipped, true),
+                ("+", Style::Plain, true),
+                ("mul(32,64]", Style::Rejected, true),
+                ("(", Style::Plain, true),
+                ("mul(11,8)", Style::Skipped, true),
+                ("un", Style::Plain, true),
+                ("do()", Style::Control, false),
+                ("mul(8,5)", Style::Counted, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_html() {
+        let html = html::<Op>("<mul(1,2)>", Config { conditionals: true });
+        assert!(html.contains(
+            "<span class=\"plain\">&lt;</span>\
+            <span class=\"counted\">mul(1,2)</span>\
+            <span class=\"plain\">&gt;</span>"
+        ));
+    }
+}
@@ -0,0 +1,124 @@
+//! A global allocator that keeps count, to see how much memory the solutions
+//! use. It's opt-in: a binary installs it with
+//!
+//! ```ignore
+//! #[global_allocator]
+//! static ALLOCATOR: aoc::alloc::Counting = aoc::alloc::Counting;
+//! ```
+//!
+//! The counters are shared by every thread, so a measurement only makes
+//! sense if nothing else runs at the same time.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicBool, AtomicU64, Ordering::Relaxed},
+};
+
+/// Whether [`Counting`] is the global allocator, i.e. has seen an allocation.
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+/// Bytes allocated since the start, including the ones freed since.
+static ALLOCATED: AtomicU64 = AtomicU64::new(0);
+static IN_USE: AtomicU64 = AtomicU64::new(0);
+/// The most bytes in use at once since the last reset by [`measure`].
+static PEAK: AtomicU64 = AtomicU64::new(0);
+
+/// The system allocator, counting allocations and bytes in use.
+#[derive(Debug)]
+pub struct Counting;
+
+fn record_allocation(size: usize) {
+    let size = size as u64;
+    INSTALLED.store(true, Relaxed);
+    ALLOCATIONS.fetch_add(1, Relaxed);
+    ALLOCATED.fetch_add(size, Relaxed);
+    let in_use = IN_USE.fetch_add(size, Relaxed) + size;
+    PEAK.fetch_max(in_use, Relaxed);
+}
+
+fn record_free(size: usize) {
+    IN_USE.fetch_sub(size as u64, Relaxed);
+}
+
+// SAFETY: every call is forwarded to the system allocator as is.
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record_allocation(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            record_allocation(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        record_free(layout.size());
+    }
+
+    /// Counts as freeing the old block and allocating the new one.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new.is_null() {
+            record_free(layout.size());
+            record_allocation(new_size);
+        }
+        new
+    }
+}
+
+/// What was allocated during a [`measure`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub allocations: u64,
+    /// Bytes allocated, including the ones freed since.
+    pub bytes: u64,
+    /// The most heap bytes in use at once, on top of what was in use before.
+    /// It isn't the resident memory, which also counts the stack and code.
+    pub peak: u64,
+}
+
+/// Calls `f`, counting what's allocated meanwhile (on any thread). Returns
+/// no stats if [`Counting`] isn't the global allocator.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Stats>) {
+    let allocations = ALLOCATIONS.load(Relaxed);
+    let allocated = ALLOCATED.load(Relaxed);
+    let base = IN_USE.load(Relaxed);
+    PEAK.store(base, Relaxed);
+
+    let value = f();
+    let stats = Stats {
+        allocations: ALLOCATIONS.load(Relaxed) - allocations,
+        bytes: ALLOCATED.load(Relaxed) - allocated,
+        peak: PEAK.load(Relaxed).saturating_sub(base),
+    };
+    (value, INSTALLED.load(Relaxed).then_some(stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+
+    #[test]
+    fn test_measure() {
+        // Other tests may allocate meanwhile, so these are lower bounds.
+        let ((), stats) = measure(|| {
+            let mut v = vec![0_u8; 1000];
+            v.extend([1, 2, 3]);
+        });
+        let stats = stats.unwrap();
+        assert!(stats.allocations >= 2, "{stats:?}");
+        assert!(stats.bytes >= 1000 + 1003, "{stats:?}");
+        assert!(stats.peak >= 1003, "{stats:?}");
+    }
+}
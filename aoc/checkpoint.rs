@@ -0,0 +1,268 @@
+//! Saving how far a long search got, so that when it's interrupted (by a
+//! timeout, or by hand) the next run resumes from there.
+
+use std::{
+    collections::BTreeSet,
+    fmt::{self, Write as _},
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Mutex, PoisonError},
+};
+
+use rayon::iter::ParallelIterator;
+
+use crate::Progress;
+
+/// Where a part saves its searches, found in its [`crate::Context`]. Nothing
+/// is saved unless the runner gives it a path.
+#[derive(Debug, Clone, Default)]
+pub struct Checkpoint {
+    path: Option<PathBuf>,
+    /// Bumped by the day when its searches change, so the saved states of the
+    /// old ones are dropped.
+    version: u32,
+    /// What the search belongs to (e.g., the day, the part and the
+    /// parameters), hashed with the key of the search.
+    scope: String,
+}
+
+/// Hashes the strings with 64-bit FNV-1a, which unlike the standard library's
+/// hashers doesn't change between releases. Each string is preceded by its
+/// length, so `["ab", "c"]` and `["a", "bc"]` differ.
+fn fnv1a(strings: &[&str]) -> u64 {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+    let mut hash = OFFSET;
+    let mut write = |bytes: &[u8]| {
+        for &byte in bytes {
+            hash = (hash ^ u64::from(byte)).wrapping_mul(PRIME);
+        }
+    };
+    for s in strings {
+        write(&(s.len() as u64).to_le_bytes());
+        write(s.as_bytes());
+    }
+    hash
+}
+
+/// How far a search got.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct State {
+    version: u32,
+    /// Tells apart the searches, so one doesn't resume another.
+    key: u64,
+    chunks: usize,
+    done: BTreeSet<usize>,
+    /// The result of the chunks done so far.
+    best: Option<String>,
+}
+
+impl State {
+    fn new(version: u32, key: u64, chunks: usize) -> Self {
+        State {
+            version,
+            key,
+            chunks,
+            done: BTreeSet::new(),
+            best: None,
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        let mut state = State::new(0, 0, 0);
+        for line in s.lines() {
+            let (field, value) = line.split_once(' ').unwrap_or((line, ""));
+            match field {
+                "version" => state.version = value.parse().ok()?,
+                "key" => state.key = u64::from_str_radix(value, 16).ok()?,
+                "chunks" => state.chunks = value.parse().ok()?,
+                "best" => state.best = Some(value.to_owned()),
+                "done" => {
+                    for chunk in value.split_whitespace() {
+                        state.done.insert(chunk.parse().ok()?);
+                    }
+                }
+                _ => return None,
+            }
+        }
+        Some(state)
+    }
+
+    fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        // Written aside and renamed, so an interruption can't leave half of
+        // it behind.
+        let partial = path.with_extension("partial");
+        fs::write(&partial, self.to_string())?;
+        fs::rename(partial, path)
+    }
+}
+
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "version {}", self.version)?;
+        writeln!(f, "key {:016x}", self.key)?;
+        writeln!(f, "chunks {}", self.chunks)?;
+        if let Some(best) = &self.best {
+            writeln!(f, "best {best}")?;
+        }
+        let mut done = String::new();
+        for chunk in &self.done {
+            write!(done, " {chunk}")?;
+        }
+        writeln!(f, "done{done}")
+    }
+}
+
+impl Checkpoint {
+    /// Saves the searches of `scope` at `path`, resuming only the ones saved
+    /// with the same `version`.
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>, version: u32, scope: impl Into<String>) -> Self {
+        Checkpoint {
+            path: Some(path.into()),
+            version,
+            scope: scope.into(),
+        }
+    }
+
+    /// The saved state of the search, if it's the same search.
+    fn load(&self, key: u64, chunks: usize) -> Option<State> {
+        let saved = fs::read_to_string(self.path.as_ref()?).ok()?;
+        State::parse(&saved).filter(|state| {
+            state.version == self.version && state.key == key && state.chunks == chunks
+        })
+    }
+
+    /// Solves the chunks `0..chunks` in parallel, combining their results
+    /// with `combine`, e.g. to find the smallest value in a huge range cut
+    /// into chunks.
+    ///
+    /// After every chunk the chunks done and the result so far are saved, and
+    /// a search with the same `key` (usually the input), scope, version and
+    /// number of chunks resumes from there. Once the search is done, the
+    /// checkpoint is removed.
+    pub fn search<T>(
+        &self,
+        key: &str,
+        chunks: usize,
+        progress: &Progress,
+        solve: impl Fn(usize) -> Option<T> + Sync,
+        combine: impl Fn(T, T) -> T + Sync,
+    ) -> Option<T>
+    where
+        T: fmt::Display + FromStr + Send,
+    {
+        let key = fnv1a(&[&self.scope, key]);
+        let new = || State::new(self.version, key, chunks);
+        let mut state = self.load(key, chunks).unwrap_or_else(new);
+        let best = match state.best.as_deref().map(str::parse) {
+            Some(Ok(best)) => Some(best),
+            // The result doesn't parse as it used to, so start over.
+            Some(Err(_)) => {
+                state = new();
+                None
+            }
+            None => None,
+        };
+        let remaining: Vec<_> = (0..chunks).filter(|i| !state.done.contains(i)).collect();
+
+        let shared = Mutex::new((state, best));
+        progress.par_iter(remaining).for_each(|i| {
+            let value = solve(i);
+            let mut shared = shared.lock().unwrap_or_else(PoisonError::into_inner);
+            let (state, best) = &mut *shared;
+            *best = match (best.take(), value) {
+                (Some(best), Some(value)) => Some(combine(best, value)),
+                (best, value) => best.or(value),
+            };
+            state.done.insert(i);
+            state.best = best.as_ref().map(ToString::to_string);
+            if let Some(path) = &self.path {
+                // Failing to save only costs redoing the chunks next time.
+                let _ = state.save(path);
+            }
+        });
+
+        if let Some(path) = &self.path {
+            let _ = fs::remove_file(path);
+        }
+        let (_, best) = shared.into_inner().unwrap_or_else(PoisonError::into_inner);
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env, panic, process,
+        sync::atomic::{AtomicUsize, Ordering::Relaxed},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_state() {
+        let mut state = State::new(2, 0xabc, 10);
+        assert_eq!(State::parse(&state.to_string()), Some(state.clone()));
+        state.done.extend([0, 3, 9]);
+        state.best = Some("42".to_owned());
+        assert_eq!(
+            state.to_string(),
+            "version 2\nkey 0000000000000abc\nchunks 10\nbest 42\ndone 0 3 9\n"
+        );
+        assert_eq!(State::parse(&state.to_string()), Some(state));
+        assert_eq!(State::parse("chunks ten\n"), None);
+    }
+
+    #[test]
+    fn test_fnv1a() {
+        // The hashes must stay the same for the saved states to be resumed.
+        assert_eq!(fnv1a(&[]), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(&["a"]), 0x529a_4ddc_8ff5_6bbf);
+        assert_ne!(fnv1a(&["ab", "c"]), fnv1a(&["a", "bc"]));
+    }
+
+    #[test]
+    fn test_resume() {
+        let path = env::temp_dir().join(format!("aoc-checkpoint-{}", process::id()));
+        let checkpoint = Checkpoint::new(&path, 1, "23/day05 part2");
+        let progress = Progress::default();
+        let solved = AtomicUsize::new(0);
+        let search = |checkpoint: &Checkpoint, fail: Option<usize>| {
+            checkpoint.search(
+                "input",
+                8,
+                &progress,
+                |i| {
+                    assert_ne!(Some(i), fail, "interrupted");
+                    solved.fetch_add(1, Relaxed);
+                    Some(100 - i)
+                },
+                usize::min,
+            )
+        };
+
+        assert!(panic::catch_unwind(|| search(&checkpoint, Some(5))).is_err());
+        let saved = State::parse(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved.done.len(), solved.load(Relaxed));
+        assert!(!saved.done.contains(&5));
+
+        // Another version or scope starts over.
+        let key = |checkpoint: &Checkpoint| fnv1a(&[&checkpoint.scope, "input"]);
+        for other in [
+            Checkpoint::new(&path, 2, "23/day05 part2"),
+            Checkpoint::new(&path, 1, "23/day05 part1"),
+        ] {
+            assert_eq!(other.load(key(&other), 8), None);
+        }
+        assert_eq!(checkpoint.load(key(&checkpoint), 8), Some(saved));
+
+        assert_eq!(search(&checkpoint, None), Some(93));
+        assert_eq!(solved.load(Relaxed), 8);
+        assert!(!path.exists());
+    }
+}
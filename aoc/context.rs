@@ -0,0 +1,175 @@
+use std::{fmt::Debug, str::FromStr};
+
+use crate::{Checkpoint, Progress};
+
+/// A named constant a solution depends on, for puzzles where it differs
+/// between the examples and the real input, or to ask "what if" questions
+/// without editing the code.
+///
+/// The value can be overridden with `--param <name>=<value>`.
+#[derive(Debug)]
+pub struct Param {
+    pub name: &'static str,
+    /// The value for the real input.
+    pub value: &'static str,
+    /// The value for the examples, if different.
+    pub example: Option<&'static str>,
+}
+
+impl Param {
+    #[must_use]
+    pub const fn new(name: &'static str, value: &'static str) -> Self {
+        Param {
+            name,
+            value,
+            example: None,
+        }
+    }
+
+    /// Uses a different value for the examples.
+    #[must_use]
+    pub const fn example(mut self, value: &'static str) -> Self {
+        self.example = Some(value);
+        self
+    }
+}
+
+/// What a part knows about the input it solves, besides its contents.
+#[derive(Debug, Clone, Default)]
+pub struct Context {
+    pub is_example: bool,
+    /// Where to report the progress of long-running parts, silent unless the
+    /// runner says otherwise.
+    pub progress: Progress,
+    /// Where to save how far long searches got, nowhere unless the runner
+    /// says otherwise.
+    pub checkpoint: Checkpoint,
+    /// Whether [`Context::set`] adds parameters that weren't declared, for
+    /// days where any name makes sense (e.g., the colors of a bag).
+    pub extra_params: bool,
+    params: Vec<(String, String)>,
+}
+
+impl Context {
+    /// Creates the context to solve the real input (or an example) with the
+    /// given parameters.
+    #[must_use]
+    pub fn new(params: &[Param], is_example: bool) -> Self {
+        let params = params
+            .iter()
+            .map(|param| {
+                let value = match param.example {
+                    Some(example) if is_example => example,
+                    _ => param.value,
+                };
+                (param.name.to_owned(), value.to_owned())
+            })
+            .collect();
+        Context {
+            is_example,
+            progress: Progress::default(),
+            checkpoint: Checkpoint::default(),
+            extra_params: false,
+            params,
+        }
+    }
+
+    /// Overrides the value of a parameter, adding it if it wasn't declared
+    /// and extra parameters are allowed.
+    ///
+    /// # Errors
+    ///
+    /// If the parameter wasn't declared and extra parameters aren't allowed,
+    /// which is most likely a typo.
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        if let Some((_, v)) = self.params.iter_mut().find(|(n, _)| n == name) {
+            value.clone_into(v);
+        } else if self.extra_params {
+            self.params.push((name.to_owned(), value.to_owned()));
+        } else if self.params.is_empty() {
+            return Err(format!("unknown parameter `{name}`, there are none"));
+        } else {
+            let names: Vec<_> = self.params.iter().map(|(n, _)| format!("`{n}`")).collect();
+            return Err(format!(
+                "unknown parameter `{name}`, expected one of {}",
+                names.join(", ")
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the parsed value of a parameter.
+    ///
+    /// # Panics
+    ///
+    /// If there is no such parameter, or if its value doesn't parse.
+    #[must_use]
+    pub fn param<T>(&self, name: &str) -> T
+    where
+        T: FromStr,
+        T::Err: Debug,
+    {
+        let Some((_, value)) = self.params.iter().find(|(n, _)| n == name) else {
+            panic!("unknown parameter `{name}`");
+        };
+        value
+            .parse()
+            .unwrap_or_else(|e| panic!("invalid value `{value}` for parameter `{name}`: {e:?}"))
+    }
+
+    /// Iterates over the names and values of all parameters, in the order
+    /// they were declared (or added).
+    pub fn params(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.params.iter().map(|(n, v)| (n.as_str(), v.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static PARAMS: &[Param] = &[
+        Param::new("width", "101").example("11"),
+        Param::new("height", "103").example("7"),
+        Param::new("steps", "100"),
+    ];
+
+    #[test]
+    fn test_example_values() {
+        let real = Context::new(PARAMS, false);
+        assert_eq!(real.param::<u32>("width"), 101);
+        assert_eq!(real.param::<u32>("steps"), 100);
+
+        let example = Context::new(PARAMS, true);
+        assert!(example.is_example);
+        assert_eq!(example.param::<u32>("width"), 11);
+        assert_eq!(example.param::<u32>("height"), 7);
+        assert_eq!(example.param::<u32>("steps"), 100);
+    }
+
+    #[test]
+    fn test_set() {
+        let mut ctx = Context::new(PARAMS, false);
+        assert_eq!(ctx.set("steps", "5"), Ok(()));
+        assert_eq!(
+            ctx.set("seed", "42"),
+            Err("unknown parameter `seed`, expected one of `width`, `height`, `steps`".to_owned())
+        );
+        assert_eq!(
+            Context::new(&[], false).set("seed", "42"),
+            Err("unknown parameter `seed`, there are none".to_owned())
+        );
+
+        ctx.extra_params = true;
+        assert_eq!(ctx.set("seed", "42"), Ok(()));
+        assert_eq!(
+            ctx.params().collect::<Vec<_>>(),
+            [
+                ("width", "101"),
+                ("height", "103"),
+                ("steps", "5"),
+                ("seed", "42")
+            ]
+        );
+    }
+}
@@ -0,0 +1,256 @@
+//! Maps keyed by small enums, backed by a fixed-size array.
+//!
+//! Unlike a `HashMap`, an [`EnumMap`] never allocates and always holds a
+//! value for every key, so a key that was never inserted reads as the
+//! default value instead of being silently absent.
+
+use std::{
+    fmt,
+    iter::Sum,
+    ops::{Add, Index, IndexMut},
+};
+
+/// A type with a small, fixed number of values, each mapped to an index in
+/// `0..LEN`.
+///
+/// Fieldless enums can implement it with [`impl_enum!`](crate::impl_enum).
+pub trait Enum: Copy {
+    /// The number of values, i.e., the length of the backing array.
+    const LEN: usize;
+
+    /// `[V; Self::LEN]`.
+    type Array<V>: AsRef<[V]> + AsMut<[V]>;
+
+    /// Builds the backing array by calling `f` with every index.
+    fn array_from_fn<V>(f: impl FnMut(usize) -> V) -> Self::Array<V>;
+
+    fn from_index(index: usize) -> Self;
+
+    fn to_index(self) -> usize;
+}
+
+/// Implements [`Enum`] for a fieldless enum, numbering the variants in the
+/// given order, which may differ from the declaration's. Every variant must
+/// be listed.
+///
+/// ```
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum Color {
+///     Red,
+///     Green,
+///     Blue,
+/// }
+///
+/// aoc::impl_enum!(Color, [Blue, Red, Green]);
+///
+/// let map = aoc::enum_map::EnumMap::from_fn(|color: Color| color == Color::Green);
+/// assert_eq!(map.values().filter(|&&v| v).count(), 1);
+/// assert!(map[Color::Green]);
+/// assert_eq!(map.values().collect::<Vec<_>>(), [&false, &false, &true]);
+/// ```
+#[macro_export]
+macro_rules! impl_enum {
+    ($name:ty, [$($variant:ident),+ $(,)?]) => {
+        impl $crate::enum_map::Enum for $name {
+            const LEN: usize = [$(stringify!($variant)),+].len();
+
+            type Array<V> = [V; <Self as $crate::enum_map::Enum>::LEN];
+
+            fn array_from_fn<V>(f: impl FnMut(usize) -> V) -> Self::Array<V> {
+                ::std::array::from_fn(f)
+            }
+
+            fn from_index(index: usize) -> Self {
+                const VARIANTS: &[$name] = &[$(<$name>::$variant),+];
+                VARIANTS[index]
+            }
+
+            fn to_index(self) -> usize {
+                // Numbers the variants in the order of the list, whatever
+                // their order (or discriminants) in the declaration.
+                enum Order {
+                    $($variant),+
+                }
+                match self {
+                    $(<$name>::$variant => Order::$variant as usize),+
+                }
+            }
+        }
+    };
+}
+
+pub struct EnumMap<K: Enum, V> {
+    values: K::Array<V>,
+}
+
+impl<K: Enum, V> EnumMap<K, V> {
+    pub fn from_fn(mut f: impl FnMut(K) -> V) -> Self {
+        EnumMap {
+            values: K::array_from_fn(|i| f(K::from_index(i))),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (K, &V)> {
+        self.values
+            .as_ref()
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (K::from_index(i), v))
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.values.as_ref().iter()
+    }
+
+    /// Combines both maps key by key.
+    pub fn zip_with<W, U>(
+        &self,
+        other: &EnumMap<K, W>,
+        mut f: impl FnMut(&V, &W) -> U,
+    ) -> EnumMap<K, U> {
+        EnumMap::from_fn(|k| f(&self[k], &other[k]))
+    }
+
+    /// Returns whether `f` holds for every pair of values under the same key.
+    pub fn all_with<W>(&self, other: &EnumMap<K, W>, mut f: impl FnMut(&V, &W) -> bool) -> bool {
+        self.values
+            .as_ref()
+            .iter()
+            .zip(other.values.as_ref())
+            .all(|(a, b)| f(a, b))
+    }
+}
+
+impl<K: Enum, V: Ord + Copy> EnumMap<K, V> {
+    /// Element-wise maximum.
+    #[must_use]
+    pub fn max(&self, other: &Self) -> Self {
+        self.zip_with(other, |&a, &b| a.max(b))
+    }
+}
+
+impl<K: Enum, V: Default> Default for EnumMap<K, V> {
+    fn default() -> Self {
+        EnumMap::from_fn(|_| V::default())
+    }
+}
+
+impl<K: Enum, V: Clone> Clone for EnumMap<K, V> {
+    fn clone(&self) -> Self {
+        EnumMap::from_fn(|k| self[k].clone())
+    }
+}
+
+impl<K: Enum, V: PartialEq> PartialEq for EnumMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.values.as_ref() == other.values.as_ref()
+    }
+}
+
+impl<K: Enum, V: Eq> Eq for EnumMap<K, V> {}
+
+impl<K: Enum + fmt::Debug, V: fmt::Debug> fmt::Debug for EnumMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Enum, V> Index<K> for EnumMap<K, V> {
+    type Output = V;
+
+    fn index(&self, key: K) -> &V {
+        &self.values.as_ref()[key.to_index()]
+    }
+}
+
+impl<K: Enum, V> IndexMut<K> for EnumMap<K, V> {
+    fn index_mut(&mut self, key: K) -> &mut V {
+        &mut self.values.as_mut()[key.to_index()]
+    }
+}
+
+/// Element-wise sum.
+impl<K: Enum, V: Add<Output = V> + Copy> Add for EnumMap<K, V> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        self.zip_with(&rhs, |&a, &b| a + b)
+    }
+}
+
+impl<K: Enum, V: Add<Output = V> + Copy + Default> Sum for EnumMap<K, V> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
+    }
+}
+
+/// Keys not present in the iterator are mapped to the default value; repeated
+/// keys keep the last value.
+impl<K: Enum, V: Default> FromIterator<(K, V)> for EnumMap<K, V> {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut map = Self::default();
+        for (k, v) in iter {
+            map[k] = v;
+        }
+        map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Dir {
+        North,
+        East,
+        South,
+        West,
+    }
+
+    crate::impl_enum!(Dir, [North, East, South, West]);
+
+    #[test]
+    fn test_index() {
+        let mut map = EnumMap::<Dir, u32>::default();
+        map[Dir::East] = 3;
+        map[Dir::West] += 1;
+        assert_eq!(map.values().copied().collect::<Vec<_>>(), [0, 3, 0, 1]);
+        assert_eq!(
+            map.iter().map(|(k, _)| k).collect::<Vec<_>>(),
+            [Dir::North, Dir::East, Dir::South, Dir::West]
+        );
+    }
+
+    #[test]
+    fn test_list_order() {
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        enum Sparse {
+            A = 10,
+            B = 20,
+        }
+        crate::impl_enum!(Sparse, [B, A]);
+
+        for (i, key) in [Sparse::B, Sparse::A].into_iter().enumerate() {
+            assert_eq!(key.to_index(), i);
+            assert_eq!(Sparse::from_index(i), key);
+        }
+        let map = EnumMap::from_fn(|key| key as u32);
+        assert_eq!((map[Sparse::A], map[Sparse::B]), (10, 20));
+    }
+
+    #[test]
+    fn test_element_wise() {
+        let a: EnumMap<Dir, u32> = [(Dir::North, 1), (Dir::South, 5)].into_iter().collect();
+        let b: EnumMap<Dir, u32> = [(Dir::North, 4), (Dir::East, 2)].into_iter().collect();
+
+        let max = a.max(&b);
+        assert_eq!(max.values().copied().collect::<Vec<_>>(), [4, 2, 5, 0]);
+
+        let sum: EnumMap<_, _> = [a.clone(), b.clone(), b].into_iter().sum();
+        assert_eq!(sum.values().copied().collect::<Vec<_>>(), [9, 4, 5, 0]);
+
+        assert!(a.all_with(&max, |x, y| x <= y));
+        assert!(!max.all_with(&a, |x, y| x <= y));
+    }
+}
@@ -0,0 +1,66 @@
+use crate::Part;
+
+/// An assumption a solution makes about its input.
+///
+/// The runner checks every invariant before solving, and skips the parts
+/// whose assumptions don't hold, instead of letting them panic on an index
+/// or silently give a wrong answer.
+///
+/// ```
+/// use aoc::{Invariant, Part};
+///
+/// fn even_count(input: &str) -> Result<(), String> {
+///     let count = input.split_whitespace().count();
+///     if count % 2 == 0 {
+///         Ok(())
+///     } else {
+///         Err(format!("found {count} numbers"))
+///     }
+/// }
+///
+/// static INVARIANTS: &[Invariant] = &[
+///     Invariant::new("numbers come in pairs", even_count).only(Part::Two),
+/// ];
+///
+/// assert!(INVARIANTS[0].applies_to(Part::Two));
+/// assert_eq!(INVARIANTS[0].check("1 2 3"), Err("found 3 numbers".to_owned()));
+/// ```
+#[derive(Debug)]
+pub struct Invariant {
+    pub description: &'static str,
+    part: Option<Part>,
+    check: fn(&str) -> Result<(), String>,
+}
+
+impl Invariant {
+    /// Creates an invariant that both parts rely on. `check` returns an
+    /// explanation of how the input breaks it.
+    pub const fn new(description: &'static str, check: fn(&str) -> Result<(), String>) -> Self {
+        Invariant {
+            description,
+            part: None,
+            check,
+        }
+    }
+
+    /// Restricts the invariant to a single part.
+    #[must_use]
+    pub const fn only(mut self, part: Part) -> Self {
+        self.part = Some(part);
+        self
+    }
+
+    #[must_use]
+    pub fn applies_to(&self, part: Part) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+
+    /// Checks the invariant against the given input.
+    ///
+    /// # Errors
+    ///
+    /// Returns why the input breaks the invariant.
+    pub fn check(&self, input: &str) -> Result<(), String> {
+        (self.check)(input)
+    }
+}
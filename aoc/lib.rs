@@ -0,0 +1,19 @@
+//! Utilities shared between the days.
+
+pub mod alloc;
+mod checkpoint;
+mod context;
+pub mod enum_map;
+mod invariant;
+mod progress;
+mod rng;
+mod solution;
+
+pub use checkpoint::Checkpoint;
+pub use context::{Context, Param};
+pub use invariant::Invariant;
+pub use progress::{Progress, ProgressMode};
+pub use rng::Rng;
+pub use solution::{
+    check_answers, check_examples, check_generate, diff, Example, Part, Solution, Variant,
+};
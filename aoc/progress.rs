@@ -0,0 +1,206 @@
+//! Showing how far along a long-running part is. Solutions wrap their
+//! iterators with [`Progress::iter`] (or [`Progress::par_iter`]), and the
+//! runner decides how the progress is shown, if at all.
+
+use std::{
+    fmt,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicU64, Ordering::Relaxed},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use console::Term;
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+
+/// How long to wait before logging anything.
+const QUIET: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ProgressMode {
+    /// Nothing is shown, e.g. in the tests.
+    #[default]
+    Silent,
+    /// A progress bar on stderr.
+    Bars,
+    /// A line on stderr every 10%, for when there's no terminal to draw on.
+    /// Nothing is logged in the first second, so quick parts stay quiet.
+    Log,
+}
+
+impl fmt::Display for ProgressMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ProgressMode::Silent => "silent",
+            ProgressMode::Bars => "bars",
+            ProgressMode::Log => "log",
+        })
+    }
+}
+
+impl FromStr for ProgressMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "silent" => Ok(ProgressMode::Silent),
+            "bars" => Ok(ProgressMode::Bars),
+            "log" => Ok(ProgressMode::Log),
+            _ => Err(format!(
+                "unknown progress `{s}`, expected `bars`, `log` or `silent`"
+            )),
+        }
+    }
+}
+
+/// Where a part reports its progress, found in its [`crate::Context`].
+#[derive(Debug, Clone, Default)]
+pub struct Progress {
+    mode: ProgressMode,
+    /// What's being solved, e.g. `23/day05 part2`.
+    label: String,
+}
+
+impl Progress {
+    #[must_use]
+    pub fn new(mode: ProgressMode, label: impl Into<String>) -> Self {
+        Progress {
+            mode,
+            label: label.into(),
+        }
+    }
+
+    /// Reports the progress of going through `iter`, as far as its length is
+    /// known.
+    pub fn iter<I: IntoIterator>(&self, iter: I) -> impl Iterator<Item = I::Item> {
+        let iter = iter.into_iter();
+        let tracker = self.tracker(iter.size_hint().1);
+        iter.inspect(move |_| {
+            if let Some(tracker) = &tracker {
+                tracker.inc();
+            }
+        })
+    }
+
+    /// Reports the progress of going through `iter` in parallel.
+    pub fn par_iter<I>(&self, iter: I) -> impl ParallelIterator<Item = I::Item>
+    where
+        I: IntoParallelIterator,
+        I::Iter: IndexedParallelIterator,
+    {
+        let iter = iter.into_par_iter();
+        let tracker = self.tracker(Some(iter.len()));
+        iter.inspect(move |_| {
+            if let Some(tracker) = &tracker {
+                tracker.inc();
+            }
+        })
+    }
+
+    fn tracker(&self, total: Option<usize>) -> Option<Arc<Tracker>> {
+        let total = total.map(|total| total as u64);
+        let bar = match self.mode {
+            ProgressMode::Silent => return None,
+            ProgressMode::Log => None,
+            ProgressMode::Bars => {
+                // Drawn even if stderr isn't a terminal, since it may be a
+                // pipe to the runner, which shows it on one.
+                let target = ProgressDrawTarget::term_like(Box::new(Term::stderr()));
+                let (bar, template) = match total {
+                    Some(total) => (
+                        ProgressBar::with_draw_target(Some(total), target),
+                        "{prefix} [{elapsed_precise}] {wide_bar} {pos}/{len} ({eta})",
+                    ),
+                    None => (
+                        ProgressBar::with_draw_target(None, target),
+                        "{prefix} [{elapsed_precise}] {spinner} {pos}",
+                    ),
+                };
+                let style = ProgressStyle::with_template(template).expect("valid template");
+                Some(bar.with_style(style).with_prefix(self.label.clone()))
+            }
+        };
+        Some(Arc::new(Tracker {
+            label: self.label.clone(),
+            total,
+            done: AtomicU64::new(0),
+            logged: AtomicU64::new(0),
+            start: Instant::now(),
+            bar,
+        }))
+    }
+}
+
+/// The progress through one iterator.
+struct Tracker {
+    label: String,
+    total: Option<u64>,
+    done: AtomicU64,
+    /// The tenths of `total` already logged.
+    logged: AtomicU64,
+    start: Instant,
+    /// The bar to draw on, or `None` to log instead.
+    bar: Option<ProgressBar>,
+}
+
+impl Tracker {
+    fn inc(&self) {
+        if let Some(bar) = &self.bar {
+            bar.inc(1);
+            return;
+        }
+        let done = self.done.fetch_add(1, Relaxed) + 1;
+        let Some(total) = self.total.filter(|&total| total > 0) else {
+            return;
+        };
+        let tenths = done * 10 / total;
+        // Only one thread logs each tenth.
+        if tenths > self.logged.load(Relaxed) && self.logged.fetch_max(tenths, Relaxed) < tenths {
+            let elapsed = self.start.elapsed();
+            if elapsed < QUIET {
+                return;
+            }
+            eprintln!(
+                "{}: {}% ({done}/{total}, {elapsed:.1?})",
+                self.label,
+                tenths * 10
+            );
+        }
+    }
+}
+
+impl Drop for Tracker {
+    fn drop(&mut self) {
+        match &self.bar {
+            Some(bar) => bar.finish_and_clear(),
+            // Without a total, there were no percentages to log.
+            None if self.total.is_none() && self.start.elapsed() >= QUIET => {
+                let done = self.done.load(Relaxed);
+                let elapsed = self.start.elapsed();
+                eprintln!("{}: done ({done}, {elapsed:.1?})", self.label);
+            }
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iter() {
+        for mode in [ProgressMode::Silent, ProgressMode::Log] {
+            let progress = Progress::new(mode, "test");
+            let sum: u32 = progress.iter(1..=10).sum();
+            assert_eq!(sum, 55);
+            let max = progress.par_iter(0..1000_u32).max();
+            assert_eq!(max, Some(999));
+        }
+        assert_eq!("log".parse(), Ok(ProgressMode::Log));
+        assert!("loud".parse::<ProgressMode>().is_err());
+    }
+}
@@ -0,0 +1,93 @@
+//! A small seeded random number generator for the input generators. It's kept
+//! here rather than taken from a crate so that a seed gives the same input on
+//! every machine and version, so a `generate` or `difftest` run can be
+//! reproduced from its seed.
+
+use std::ops::Range;
+
+/// A `SplitMix64` generator.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `range`, which must not be empty. The numbers are very
+    /// slightly biased, which doesn't matter for generating inputs.
+    ///
+    /// # Panics
+    ///
+    /// If the range is empty.
+    pub fn range(&mut self, range: Range<u64>) -> u64 {
+        assert!(!range.is_empty(), "empty range {range:?}");
+        let len = range.end - range.start;
+        let high = (u128::from(self.next_u64()) * u128::from(len)) >> 64;
+        range.start + u64::try_from(high).expect("below `len`")
+    }
+
+    /// An index in `0..len`, which must not be empty.
+    ///
+    /// # Panics
+    ///
+    /// If `len` is 0.
+    pub fn index(&mut self, len: usize) -> usize {
+        let index = self.range(0..len as u64);
+        usize::try_from(index).expect("below `len`")
+    }
+
+    /// Whether something that happens one time in `n` happened.
+    pub fn one_in(&mut self, n: u64) -> bool {
+        self.range(0..n) == 0
+    }
+
+    /// One of `items`, which must not be empty.
+    ///
+    /// # Panics
+    ///
+    /// If `items` is empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rng() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..100).map(|_| rng.range(10..20)).collect::<Vec<_>>()
+        };
+        assert_eq!(numbers(1), numbers(1));
+        assert_ne!(numbers(1), numbers(2));
+        assert!(numbers(1).iter().all(|n| (10..20).contains(n)));
+        // Every number comes up.
+        assert!((10..20).all(|n| numbers(1).contains(&n)));
+
+        let mut items: Vec<_> = (0..10).collect();
+        Rng::new(0).shuffle(&mut items);
+        items.sort_unstable();
+        assert_eq!(items, (0..10).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,439 @@
+use std::fmt::{self, Write as _};
+
+use crate::{Context, Invariant, Param, Rng};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => f.write_str("part1"),
+            Part::Two => f.write_str("part2"),
+        }
+    }
+}
+
+/// Everything the runner needs to know about a day.
+///
+/// Only the input and the parts are required, the rest is added with the
+/// `with_*` methods:
+///
+/// ```
+/// static SOLUTION: aoc::Solution = aoc::Solution::new(
+///     "1 2 3",
+///     |input, _| input.split(' ').count().to_string(),
+///     |input, _| input.replace(' ', "").to_string(),
+/// )
+/// .with_answers([Some("3"), Some("123")]);
+///
+/// aoc::check_answers(&SOLUTION);
+/// ```
+#[derive(Debug)]
+pub struct Solution {
+    pub input: &'static str,
+    pub part1: fn(&str, &Context) -> String,
+    pub part2: fn(&str, &Context) -> String,
+    /// Parses the input without solving anything, to measure parsing apart
+    /// from the parts (which parse on their own).
+    pub parse: Option<fn(&str)>,
+    /// Other ways to solve the parts, checked against the main ones.
+    pub variants: &'static [Variant],
+    /// Generates a random valid input of about the given size (what the size
+    /// counts, e.g. lines or games, is up to the day).
+    pub generate: Option<fn(&mut Rng, usize) -> String>,
+    pub params: &'static [Param],
+    /// Whether `--param` may add parameters besides `params`.
+    pub extra_params: bool,
+    /// Bumped when the searches of the parts change, so the runner doesn't
+    /// resume the ones saved before (see [`crate::Checkpoint`]).
+    pub checkpoint_version: u32,
+    pub invariants: &'static [Invariant],
+    /// The accepted answers for the real input, once known.
+    pub answers: [Option<&'static str>; 2],
+    pub examples: &'static [Example],
+}
+
+/// Another way to solve a part, e.g. a naive version kept next to an
+/// optimized one. The tests check that it gives the same answers on the
+/// examples, and the runner (with `--variants`) on the real input too.
+#[derive(Debug)]
+pub struct Variant {
+    pub name: &'static str,
+    pub part: Part,
+    pub solve: fn(&str, &Context) -> String,
+}
+
+impl Variant {
+    #[must_use]
+    pub const fn new(name: &'static str, part: Part, solve: fn(&str, &Context) -> String) -> Self {
+        Variant { name, part, solve }
+    }
+}
+
+/// A sample input from the puzzle statement, usually kept next to the day as
+/// `exampleN.txt`.
+#[derive(Debug)]
+pub struct Example {
+    pub input: &'static str,
+    /// The answers the puzzle statement gives for this example, if any. Some
+    /// examples only illustrate one of the parts.
+    pub answers: [Option<&'static str>; 2],
+}
+
+impl Example {
+    #[must_use]
+    pub fn answer(&self, part: Part) -> Option<&'static str> {
+        match part {
+            Part::One => self.answers[0],
+            Part::Two => self.answers[1],
+        }
+    }
+
+    /// Whether the example is meant for the part. Examples without any
+    /// answers yet are meant for both.
+    #[must_use]
+    pub fn illustrates(&self, part: Part) -> bool {
+        self.answer(part).is_some() || self.answers.iter().all(Option::is_none)
+    }
+}
+
+impl Solution {
+    #[must_use]
+    pub const fn new(
+        input: &'static str,
+        part1: fn(&str, &Context) -> String,
+        part2: fn(&str, &Context) -> String,
+    ) -> Self {
+        Solution {
+            input,
+            part1,
+            part2,
+            parse: None,
+            variants: &[],
+            generate: None,
+            params: &[],
+            extra_params: false,
+            checkpoint_version: 0,
+            invariants: &[],
+            answers: [None, None],
+            examples: &[],
+        }
+    }
+
+    #[must_use]
+    pub const fn with_parse(mut self, parse: fn(&str)) -> Self {
+        self.parse = Some(parse);
+        self
+    }
+
+    #[must_use]
+    pub const fn with_variants(mut self, variants: &'static [Variant]) -> Self {
+        self.variants = variants;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_generator(mut self, generate: fn(&mut Rng, usize) -> String) -> Self {
+        self.generate = Some(generate);
+        self
+    }
+
+    #[must_use]
+    pub const fn with_params(mut self, params: &'static [Param]) -> Self {
+        self.params = params;
+        self
+    }
+
+    /// Accepts parameters besides the declared ones (see
+    /// [`Context::extra_params`]).
+    #[must_use]
+    pub const fn with_extra_params(mut self) -> Self {
+        self.extra_params = true;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_checkpoint_version(mut self, version: u32) -> Self {
+        self.checkpoint_version = version;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_invariants(mut self, invariants: &'static [Invariant]) -> Self {
+        self.invariants = invariants;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_answers(mut self, answers: [Option<&'static str>; 2]) -> Self {
+        self.answers = answers;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_examples(mut self, examples: &'static [Example]) -> Self {
+        self.examples = examples;
+        self
+    }
+
+    /// Creates the context to solve the real input (or an example) with the
+    /// default parameters.
+    #[must_use]
+    pub fn context(&self, is_example: bool) -> Context {
+        let mut ctx = Context::new(self.params, is_example);
+        ctx.extra_params = self.extra_params;
+        ctx
+    }
+
+    #[must_use]
+    pub fn part(&self, part: Part) -> fn(&str, &Context) -> String {
+        match part {
+            Part::One => self.part1,
+            Part::Two => self.part2,
+        }
+    }
+
+    /// Returns the other ways to solve the part.
+    pub fn variants(&self, part: Part) -> impl Iterator<Item = &Variant> {
+        self.variants
+            .iter()
+            .filter(move |variant| variant.part == part)
+    }
+
+    #[must_use]
+    pub fn variant(&self, part: Part, name: &str) -> Option<&Variant> {
+        self.variants(part).find(|variant| variant.name == name)
+    }
+
+    /// Returns the accepted answer for the real input, if known.
+    #[must_use]
+    pub fn answer(&self, part: Part) -> Option<&'static str> {
+        match part {
+            Part::One => self.answers[0],
+            Part::Two => self.answers[1],
+        }
+    }
+
+    /// Returns the invariants the part relies on that the input breaks, along
+    /// with the reason they're broken.
+    #[must_use]
+    pub fn broken_invariants(&self, part: Part, input: &str) -> Vec<(&Invariant, String)> {
+        self.invariants
+            .iter()
+            .filter(|invariant| invariant.applies_to(part))
+            .filter_map(|invariant| Some((invariant, invariant.check(input).err()?)))
+            .collect()
+    }
+}
+
+/// Checks that every part with a known answer still gives it on the real
+/// input. Meant to be called from each day's `test_answers`.
+///
+/// # Panics
+///
+/// If a part gives a different answer, or if the input breaks one of its
+/// invariants.
+pub fn check_answers(solution: &Solution) {
+    let input = solution.input;
+    for part in Part::ALL {
+        let Some(expected) = solution.answer(part) else {
+            continue;
+        };
+        let broken = solution.broken_invariants(part, input);
+        assert!(
+            broken.is_empty(),
+            "{part}: input breaks assumptions: {broken:?}"
+        );
+        let answer = (solution.part(part))(input, &solution.context(false));
+        assert_eq!(answer, expected, "wrong answer for {part}");
+    }
+}
+
+/// Checks that the part, and each of its variants, gives the expected answer
+/// on every example that has one. Meant to be called from each day's
+/// `test_part1` and `test_part2`.
+///
+/// # Panics
+///
+/// If the part gives a different answer on any example, showing a diff.
+pub fn check_examples(solution: &Solution, part: Part) {
+    let variants = solution
+        .variants(part)
+        .map(|variant| (format!("{part} ({})", variant.name), variant.solve));
+    let solves: Vec<_> = [(part.to_string(), solution.part(part))]
+        .into_iter()
+        .chain(variants)
+        .collect();
+    for (i, example) in solution.examples.iter().enumerate() {
+        let Some(expected) = example.answer(part) else {
+            continue;
+        };
+        for (name, solve) in &solves {
+            let answer = solve(example.input, &solution.context(true));
+            assert!(
+                answer == expected,
+                "wrong answer for {name} on example {}:\n{}",
+                i + 1,
+                diff(expected, &answer)
+            );
+        }
+    }
+}
+
+/// Checks that the day's generator gives the same input for the same seed,
+/// and inputs that keep the invariants and that every part (and variant) can
+/// solve. Meant to be called from each day's `test_generate`.
+///
+/// # Panics
+///
+/// If the day has no generator, or if a generated input is rejected.
+pub fn check_generate(solution: &Solution) {
+    let generate = solution.generate.expect("the day has a generator");
+    for seed in 0..10 {
+        let input = generate(&mut Rng::new(seed), 10);
+        assert_eq!(
+            input,
+            generate(&mut Rng::new(seed), 10),
+            "seed {seed}: the input changed"
+        );
+        for part in Part::ALL {
+            let broken = solution.broken_invariants(part, &input);
+            assert!(
+                broken.is_empty(),
+                "seed {seed}: {part}: input breaks assumptions: {broken:?}\n{input}"
+            );
+            let ctx = solution.context(false);
+            (solution.part(part))(&input, &ctx);
+            for variant in solution.variants(part) {
+                (variant.solve)(&input, &ctx);
+            }
+        }
+    }
+}
+
+/// Renders a line by line diff between an expected and an actual answer,
+/// marking the expected lines with `-` and the actual ones with `+`. Lines
+/// that match are kept for context, since some answers are drawings.
+#[must_use]
+pub fn diff(expected: &str, actual: &str) -> String {
+    let mut expected = expected.lines();
+    let mut actual = actual.lines();
+    let mut out = String::new();
+    loop {
+        match (expected.next(), actual.next()) {
+            (None, None) => break,
+            (Some(e), Some(a)) if e == a => {
+                let _ = writeln!(out, "  {e}");
+            }
+            (e, a) => {
+                if let Some(e) = e {
+                    let _ = writeln!(out, "- {e}");
+                }
+                if let Some(a) = a {
+                    let _ = writeln!(out, "+ {a}");
+                }
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn non_empty(input: &str) -> Result<(), String> {
+        if input.is_empty() {
+            Err("input is empty".to_owned())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn short(input: &str) -> Result<(), String> {
+        if input.len() < 4 {
+            Ok(())
+        } else {
+            Err(format!("input has {} bytes", input.len()))
+        }
+    }
+
+    static SOLUTION: Solution =
+        Solution::new("", |input, _| input.to_owned(), |input, _| input.to_owned())
+            .with_invariants(&[
+                Invariant::new("input is not empty", non_empty),
+                Invariant::new("input is short", short).only(Part::Two),
+            ]);
+
+    #[test]
+    fn test_broken_invariants() {
+        let descriptions = |part, input| {
+            SOLUTION
+                .broken_invariants(part, input)
+                .into_iter()
+                .map(|(invariant, reason)| (invariant.description, reason))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(descriptions(Part::One, "abc"), []);
+        assert_eq!(descriptions(Part::Two, "abc"), []);
+        assert_eq!(
+            descriptions(Part::One, ""),
+            [("input is not empty", "input is empty".to_owned())]
+        );
+        assert_eq!(descriptions(Part::One, "abcd"), []);
+        assert_eq!(
+            descriptions(Part::Two, "abcd"),
+            [("input is short", "input has 4 bytes".to_owned())]
+        );
+    }
+
+    #[test]
+    fn test_check_answers() {
+        let reversed = Solution::new(
+            "abc",
+            |input, _| input.chars().rev().collect(),
+            |input, _| input.to_uppercase(),
+        )
+        .with_answers([Some("cba"), None])
+        .with_examples(&[
+            Example {
+                input: "xy",
+                answers: [Some("yx"), Some("XY")],
+            },
+            Example {
+                input: "z",
+                answers: [None, Some("Z")],
+            },
+        ]);
+        check_answers(&reversed);
+        check_examples(&reversed, Part::One);
+        check_examples(&reversed, Part::Two);
+
+        let wrong = Solution {
+            answers: [Some("cba"), Some("abc")],
+            ..reversed
+        };
+        assert!(std::panic::catch_unwind(|| check_answers(&wrong)).is_err());
+
+        let wrong = Solution {
+            part2: |input, _| input.to_owned(),
+            ..reversed
+        };
+        assert!(std::panic::catch_unwind(|| check_examples(&wrong, Part::Two)).is_err());
+    }
+
+    #[test]
+    fn test_diff() {
+        assert_eq!(diff("42", "41"), "- 42\n+ 41\n");
+        assert_eq!(diff("#.\n.#", "#.\n##\n.."), "  #.\n- .#\n+ ##\n+ ..\n");
+    }
+}
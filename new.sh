@@ -0,0 +1,34 @@
+#!/usr/bin/env bash
+
+set -euo pipefail
+
+if [ $# -ne 1 ]; then
+    echo "usage: $0 <year>" >&2
+    exit 1
+fi
+
+year="$1"
+mkdir -p "$year"
+
+last=$(
+    find "$year" -type d -maxdepth 1 -name 'day*' |\
+    sort -r |\
+    head -n 1 |\
+    sed -n -e 's/^.*\/day0*\([[:digit:]]\)/\1/p'
+)
+
+today_num="$(printf "%02d" "$((last + 1))")"
+today_dir="$year/day$today_num"
+
+echo "Creating directory '$today_dir'"
+mkdir "$today_dir"
+
+sed -e "s/{name}/y$year-day$today_num/g" template/Cargo.toml >> "$today_dir/Cargo.toml"
+cp template/{input.txt,example1.txt,lib.rs} "$today_dir"
+
+echo "Registering it in the runner"
+name="y$year-day$today_num"
+echo "$name = { path = \"../$today_dir\" }" >> runner/Cargo.toml
+sed -i -e "s|^];|    Day::new($year, $((last + 1)), \&${name//-/_}::SOLUTION),\n];|" runner/days.rs
+
+echo "ok"
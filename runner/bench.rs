@@ -0,0 +1,321 @@
+//! Timing the parts over several runs, and keeping the timings in a history
+//! file to catch the parts that got slower than their recent timings.
+//!
+//! The history has a JSON object per line, and is only compared between runs
+//! on the same machine.
+
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    process::Command,
+    time::Duration,
+};
+
+use aoc::Part;
+use serde_json::{json, Value};
+
+use crate::{find, isolate, report::Format, report::Stage, root, Day, Options, DAYS};
+
+/// The history file, in the root of the workspace.
+const HISTORY: &str = "bench-history.jsonl";
+/// How many times each part is solved, unless given with `--samples`.
+const DEFAULT_SAMPLES: usize = 10;
+/// How much slower than the baseline a part may get, in percent, unless given
+/// with `--threshold`.
+const DEFAULT_THRESHOLD: f64 = 10.0;
+/// How many of the latest timings of a part the baseline is picked from.
+const BASELINE_WINDOW: usize = 5;
+
+/// The timing of a part, as kept in the history.
+#[derive(Debug, Clone, PartialEq)]
+struct Entry {
+    /// The day, as in [`Day`]'s `Display`.
+    day: String,
+    part: Part,
+    median: Duration,
+    /// The git revision, with `-dirty` if there were uncommitted changes.
+    rev: String,
+    machine: String,
+}
+
+impl Entry {
+    fn to_json(&self) -> Value {
+        json!({
+            "day": self.day,
+            "part": self.part.to_string(),
+            "median_ns": self.median.as_nanos(),
+            "rev": self.rev,
+            "machine": self.machine,
+        })
+    }
+
+    fn from_json(value: &Value) -> Option<Self> {
+        let part = match value["part"].as_str()? {
+            "part1" => Part::One,
+            "part2" => Part::Two,
+            _ => return None,
+        };
+        Some(Entry {
+            day: value["day"].as_str()?.to_owned(),
+            part,
+            median: Duration::from_nanos(value["median_ns"].as_u64()?),
+            rev: value["rev"].as_str()?.to_owned(),
+            machine: value["machine"].as_str()?.to_owned(),
+        })
+    }
+}
+
+fn load(path: &Path) -> Result<Vec<Entry>, String> {
+    let history = match fs::read_to_string(path) {
+        Ok(history) => history,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("couldn't read {}: {e}", path.display())),
+    };
+    history
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line)
+                .ok()
+                .as_ref()
+                .and_then(Entry::from_json)
+                .ok_or_else(|| format!("invalid entry on line {} of {}", i + 1, path.display()))
+        })
+        .collect()
+}
+
+fn append(path: &Path, entries: &[Entry]) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    for entry in entries {
+        writeln!(file, "{}", entry.to_json())?;
+    }
+    Ok(())
+}
+
+/// The fastest of the latest timings of the part on the machine, at committed
+/// revisions. A regression doesn't become the baseline of the next run, unless
+/// it lasts for the whole window.
+fn baseline<'a>(history: &'a [Entry], day: &str, part: Part, machine: &str) -> Option<&'a Entry> {
+    history
+        .iter()
+        .rev()
+        .filter(|entry| entry.day == day && entry.part == part && entry.machine == machine)
+        .filter(|entry| !entry.rev.ends_with("-dirty"))
+        .take(BASELINE_WINDOW)
+        .min_by_key(|entry| entry.median)
+}
+
+/// How much slower `median` is than the baseline, in percent (negative if
+/// it's faster).
+fn change(median: Duration, baseline: Duration) -> f64 {
+    (median.as_secs_f64() / baseline.as_secs_f64() - 1.0) * 100.0
+}
+
+fn median(times: &mut [Duration]) -> Duration {
+    times.sort_unstable();
+    let middle = times.len() / 2;
+    if times.len().is_multiple_of(2) {
+        (times[middle - 1] + times[middle]) / 2
+    } else {
+        times[middle]
+    }
+}
+
+/// The current git revision, or `unknown` outside of a repository.
+fn git_rev() -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .current_dir(root())
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_owned())
+    };
+    let Some(rev) = git(&["rev-parse", "--short", "HEAD"]) else {
+        return "unknown".to_owned();
+    };
+    let dirty = git(&["status", "--porcelain"]).is_some_and(|status| !status.is_empty());
+    if dirty {
+        format!("{rev}-dirty")
+    } else {
+        rev
+    }
+}
+
+/// The host name, which tells apart the machines the history comes from.
+fn machine() -> String {
+    let mut name = [0_u8; 256];
+    // SAFETY: the buffer is as long as it's said to be.
+    let result = unsafe { libc::gethostname(name.as_mut_ptr().cast(), name.len()) };
+    if result != 0 {
+        return "unknown".to_owned();
+    }
+    let len = name.iter().position(|&b| b == 0).unwrap_or(name.len());
+    String::from_utf8_lossy(&name[..len]).into_owned()
+}
+
+/// Solves the part `samples` times on the real input, returning the median
+/// time, or why it couldn't be solved.
+fn time(day: &Day, part: Part, samples: usize, options: &Options) -> Result<Duration, String> {
+    let solution = day.solution;
+    if !solution.broken_invariants(part, solution.input).is_empty() {
+        return Err("the input breaks its assumptions".to_owned());
+    }
+    let mut times = Vec::with_capacity(samples);
+    for _ in 0..samples {
+        let run = if options.in_process {
+            let ctx = solution.context(false);
+            isolate::solve_here(|| Some((solution.part(part))(solution.input, &ctx)))
+        } else {
+            isolate::solve_in_child(day, Stage::Part(part), "input", options)
+                .map_err(|e| format!("couldn't start the child: {e}"))?
+        };
+        match run.outcome {
+            isolate::Outcome::Solved(_) => times.push(run.time),
+            isolate::Outcome::Panicked(panic) => return Err(panic),
+            isolate::Outcome::TimedOut => return Err("timed out".to_owned()),
+            isolate::Outcome::OutOfMemory => return Err("ran out of memory".to_owned()),
+        }
+    }
+    Ok(median(&mut times))
+}
+
+/// Times the parts of the day (or of every day with `--all`), one at a time
+/// so they don't compete for the CPU, and appends the timings to the history.
+/// Returns whether no part failed or got slower than the threshold.
+pub fn bench(options: &Options) -> Result<bool, String> {
+    if options.example || !options.params.is_empty() {
+        return Err("`bench` only times the real input with the default parameters".to_owned());
+    }
+    let samples = options.samples.unwrap_or(DEFAULT_SAMPLES);
+    if samples == 0 {
+        return Err("`--samples` must be at least 1".to_owned());
+    }
+    let threshold = options.threshold.unwrap_or(DEFAULT_THRESHOLD);
+    let days: Vec<&Day> = match (options.day, options.all) {
+        (Some(_), true) | (None, false) => return Err("expected `--day` or `--all`".to_owned()),
+        (Some(day), false) => vec![find(DAYS, options.year, day)?],
+        (None, true) => DAYS
+            .iter()
+            .filter(|day| options.year.is_none_or(|year| day.year == year))
+            .collect(),
+    };
+
+    let path = root().join(HISTORY);
+    let history = load(&path)?;
+    let rev = git_rev();
+    let machine = machine();
+    let mut ok = true;
+    let mut entries = Vec::new();
+    let mut results = Vec::new();
+    for day in days {
+        for part in Part::ALL {
+            let name = day.to_string();
+            let median = match time(day, part, samples, options) {
+                Ok(median) => median,
+                Err(reason) => {
+                    ok = false;
+                    eprintln!("{name} {part}: {reason}");
+                    continue;
+                }
+            };
+            let baseline = baseline(&history, &name, part, &machine);
+            let change = baseline.map(|baseline| change(median, baseline.median));
+            let regressed = change.is_some_and(|change| change > threshold);
+            ok &= !regressed;
+            if options.format == Format::Text {
+                match (baseline, change) {
+                    (Some(baseline), Some(change)) => {
+                        let flag = if regressed { ", regression" } else { "" };
+                        println!(
+                            "{name} {part}: {median:.1?} ({change:+.1}% from {:.1?} at {}{flag})",
+                            baseline.median, baseline.rev
+                        );
+                    }
+                    _ => println!("{name} {part}: {median:.1?} (no baseline)"),
+                }
+            }
+            let entry = Entry {
+                day: name,
+                part,
+                median,
+                rev: rev.clone(),
+                machine: machine.clone(),
+            };
+            let mut result = entry.to_json();
+            result["baseline"] = json!(baseline.map(Entry::to_json));
+            result["regressed"] = json!(regressed);
+            results.push(result);
+            entries.push(entry);
+        }
+    }
+    if options.format == Format::Json {
+        println!("{}", Value::Array(results));
+    }
+    append(&path, &entries).map_err(|e| format!("couldn't write {}: {e}", path.display()))?;
+    Ok(ok)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(day: &str, part: Part, millis: u64, rev: &str, machine: &str) -> Entry {
+        Entry {
+            day: day.to_owned(),
+            part,
+            median: Duration::from_millis(millis),
+            rev: rev.to_owned(),
+            machine: machine.to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_median() {
+        let ms = Duration::from_millis;
+        assert_eq!(median(&mut [ms(3), ms(1), ms(2)]), ms(2));
+        assert_eq!(median(&mut [ms(4), ms(1), ms(2), ms(8)]), ms(3));
+    }
+
+    #[test]
+    fn test_baseline() {
+        let history = [
+            entry("23/day04", Part::One, 10, "a", "laptop"),
+            entry("23/day04", Part::Two, 20, "a", "laptop"),
+            entry("23/day04", Part::One, 8, "b", "laptop"),
+            entry("23/day04", Part::One, 2, "b", "server"),
+            entry("23/day04", Part::One, 5, "c-dirty", "laptop"),
+        ];
+        for entry in &history {
+            assert_eq!(Entry::from_json(&entry.to_json()).as_ref(), Some(entry));
+        }
+
+        let found = baseline(&history, "23/day04", Part::One, "laptop").unwrap();
+        assert_eq!(found, &history[2]);
+        assert!(baseline(&history, "23/day05", Part::One, "laptop").is_none());
+        assert!(baseline(&history, "23/day04", Part::Two, "server").is_none());
+
+        let change = change(Duration::from_millis(10), found.median);
+        assert!((change - 25.0).abs() < 1e-9, "{change}");
+    }
+
+    #[test]
+    fn test_baseline_after_regression() {
+        let mut history = vec![
+            entry("23/day04", Part::One, 10, "a", "laptop"),
+            entry("23/day04", Part::One, 30, "b", "laptop"),
+        ];
+        let found = baseline(&history, "23/day04", Part::One, "laptop").unwrap();
+        assert_eq!(found, &history[0]);
+
+        // Until the regression fills the window.
+        for _ in 1..BASELINE_WINDOW {
+            history.push(entry("23/day04", Part::One, 30, "c", "laptop"));
+        }
+        let found = baseline(&history, "23/day04", Part::One, "laptop").unwrap();
+        assert_eq!(found.median, Duration::from_millis(30));
+    }
+}
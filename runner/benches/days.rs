@@ -0,0 +1,37 @@
+//! Benchmarks the parsing and both parts of every day on its real input, e.g.
+//! `cargo bench --package runner -- 23/day04`.
+//!
+//! Parts without an accepted answer are left out, since they may not even
+//! finish (like the brute force of 23/day05's part 2).
+
+use std::hint::black_box;
+
+use aoc::Part;
+use criterion::{criterion_group, criterion_main, Criterion};
+use runner::DAYS;
+
+fn days(c: &mut Criterion) {
+    for day in DAYS {
+        let solution = day.solution;
+        let mut group = c.benchmark_group(day.to_string());
+        if let Some(parse) = solution.parse {
+            group.bench_function("parse", |b| b.iter(|| parse(black_box(solution.input))));
+        }
+        let ctx = solution.context(false);
+        for part in Part::ALL {
+            if solution.answer(part).is_none()
+                || !solution.broken_invariants(part, solution.input).is_empty()
+            {
+                continue;
+            }
+            let f = solution.part(part);
+            group.bench_function(part.to_string(), |b| {
+                b.iter(|| f(black_box(solution.input), &ctx));
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
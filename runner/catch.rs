@@ -0,0 +1,82 @@
+//! Catching panics of the solutions, so a bug in one part doesn't take down
+//! the others.
+
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    panic::{self, AssertUnwindSafe},
+    sync::Once,
+};
+
+thread_local! {
+    /// How many calls to [`catch`] this thread is in.
+    static CATCHING: Cell<usize> = const { Cell::new(0) };
+    /// The message and location of the last panic caught on this thread.
+    static CAUGHT: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Installs the panic hook that records the location of caught panics, which
+/// the payload alone doesn't have. Panics that aren't caught are still
+/// printed as usual.
+pub fn install_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING.get() == 0 {
+                default(info);
+                return;
+            }
+            let message = info.payload_as_str().unwrap_or("unknown panic");
+            let caught = match info.location() {
+                Some(location) => format!("panicked at {location}: {message}"),
+                None => format!("panicked: {message}"),
+            };
+            CAUGHT.set(Some(caught));
+        }));
+    });
+}
+
+fn payload_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        format!("panicked: {message}")
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        format!("panicked: {message}")
+    } else {
+        "panicked".to_owned()
+    }
+}
+
+/// Calls `f`, turning a panic into a description with its message and, if
+/// the hook is installed, its location.
+///
+/// Panics on other threads (e.g., of `rayon`) are printed by the default hook
+/// when they happen, and only their message is known once they reach `f`.
+pub fn catch<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    CATCHING.set(CATCHING.get() + 1);
+    CAUGHT.set(None);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(CATCHING.get() - 1);
+    result.map_err(|payload| CAUGHT.take().unwrap_or_else(|| payload_message(&*payload)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catch() {
+        install_hook();
+        assert_eq!(catch(|| 42), Ok(42));
+
+        let line = line!() + 1;
+        let error = catch(|| panic!("boom {}", 42)).unwrap_err();
+        assert_eq!(
+            error,
+            format!("panicked at runner/catch.rs:{line}:30: boom 42")
+        );
+
+        let nested = catch(|| catch(|| panic!("inner")).unwrap_err() + " and outer");
+        assert!(nested.unwrap().ends_with("inner and outer"));
+    }
+}
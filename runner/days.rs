@@ -0,0 +1,12 @@
+//! Every day the runner knows about, added by `new.sh`.
+
+use crate::Day;
+
+pub static DAYS: &[Day] = &[
+    Day::new(23, 1, &y23_day01::SOLUTION),
+    Day::new(23, 2, &y23_day02::SOLUTION),
+    Day::new(23, 3, &y23_day03::SOLUTION),
+    Day::new(23, 4, &y23_day04::SOLUTION),
+    Day::new(23, 5, &y23_day05::SOLUTION),
+    Day::new(24, 3, &y24_day03::SOLUTION),
+];
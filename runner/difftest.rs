@@ -0,0 +1,220 @@
+//! Differential testing: solving many generated inputs with every variant of
+//! a part, to find an input on which they disagree with the part.
+//!
+//! Such an input is shrunk (see [`shrink`]) and saved in the `regressions`
+//! directory of the day, where the tests keep checking it.
+
+use std::{fs, path::PathBuf};
+
+use aoc::{Context, Part, Rng, Variant};
+
+use crate::{catch, context, find, Day, Options, DAYS, DEFAULT_SIZE};
+
+/// How many inputs are generated, unless given with `--cases`.
+const DEFAULT_CASES: usize = 100;
+
+/// What solving an input gave: the answer, or the panic.
+type Outcome = Result<String, String>;
+
+/// How the part and the variant disagreed, which the shrunk inputs have to
+/// keep: a panic on a shrunk input that used to be solved is likely just an
+/// input that's no longer valid.
+fn same_kind(a: (&Outcome, &Outcome), b: (&Outcome, &Outcome)) -> bool {
+    a.0.is_ok() == b.0.is_ok() && a.1.is_ok() == b.1.is_ok()
+}
+
+/// A part and one of its variants, to solve inputs with.
+struct Pair<'a> {
+    day: &'a Day,
+    part: Part,
+    variant: &'a Variant,
+    ctx: &'a Context,
+}
+
+impl Pair<'_> {
+    /// Solves the input with the part and the variant, or returns `None` if
+    /// the input breaks the invariants of the part.
+    fn solve(&self, input: &str) -> Option<(Outcome, Outcome)> {
+        let solution = self.day.solution;
+        if !solution.broken_invariants(self.part, input).is_empty() {
+            return None;
+        }
+        let main = catch::catch(|| (solution.part(self.part))(input, self.ctx));
+        let variant = catch::catch(|| (self.variant.solve)(input, self.ctx));
+        Some((main, variant))
+    }
+
+    /// Whether the part and the variant disagree on the input in the same way
+    /// as `outcomes`.
+    fn disagree(&self, input: &str, outcomes: (&Outcome, &Outcome)) -> bool {
+        self.solve(input).is_some_and(|(main, variant)| {
+            main != variant && same_kind((&main, &variant), outcomes)
+        })
+    }
+}
+
+/// Shrinks the input while `keep` holds, by removing chunks of lines and then
+/// of characters, halving the chunks whenever none can be removed.
+fn shrink(input: &str, keep: impl Fn(&str) -> bool) -> String {
+    let lines: Vec<String> = input.lines().map(|line| format!("{line}\n")).collect();
+    let input = shrink_units(lines, &keep);
+    let chars: Vec<String> = input.chars().map(String::from).collect();
+    shrink_units(chars, &keep)
+}
+
+fn shrink_units(mut units: Vec<String>, keep: &impl Fn(&str) -> bool) -> String {
+    let mut chunk = units.len() / 2;
+    while chunk > 0 {
+        let mut start = 0;
+        let mut removed = false;
+        while start < units.len() {
+            let end = units.len().min(start + chunk);
+            let candidate = [&units[..start], &units[end..]].concat().concat();
+            if keep(&candidate) {
+                units.drain(start..end);
+                removed = true;
+            } else {
+                start = end;
+            }
+        }
+        if !removed {
+            chunk /= 2;
+        }
+    }
+    units.concat()
+}
+
+/// Describes an outcome for the output.
+fn describe(outcome: &Outcome) -> &str {
+    match outcome {
+        Ok(answer) => answer,
+        Err(panic) => panic,
+    }
+}
+
+/// The directory of the day's regression inputs.
+fn regressions(day: &Day) -> PathBuf {
+    day.dir().join("regressions")
+}
+
+/// Solves generated inputs of growing sizes (up to `--size`) with every
+/// variant of the day, saving the shrunk input of each variant that disagrees
+/// with its part. Returns whether all of them agreed.
+pub fn difftest(options: &Options) -> Result<bool, String> {
+    let day = options.day.ok_or("missing `--day`")?;
+    let day = find(DAYS, options.year, day)?;
+    let solution = day.solution;
+    let generate = solution
+        .generate
+        .ok_or_else(|| format!("{day} has no generator"))?;
+    if solution.variants.is_empty() {
+        return Err(format!("{day} has no variants to compare"));
+    }
+    let cases = options.cases.unwrap_or(DEFAULT_CASES);
+    let size = options.size.unwrap_or(DEFAULT_SIZE);
+    if cases == 0 || size == 0 {
+        return Err("`--cases` and `--size` must be at least 1".to_owned());
+    }
+    let seed = options.seed.unwrap_or(0);
+    let ctx = context(solution, options, false).map_err(|e| format!("{day}: {e}"))?;
+
+    let mut ok = true;
+    for part in Part::ALL {
+        for variant in solution.variants(part) {
+            let pair = Pair {
+                day,
+                part,
+                variant,
+                ctx: &ctx,
+            };
+            let name = format!("{day} {part} ({})", variant.name);
+            let found = (0..cases).find_map(|case| {
+                let seed = seed.wrapping_add(case as u64);
+                // The small inputs come first, since they're quicker and
+                // easier to make sense of.
+                let input = generate(&mut Rng::new(seed), 1 + case * size / cases);
+                let (main, other) = pair.solve(&input)?;
+                (main != other).then_some((seed, input, main, other))
+            });
+            let Some((seed, input, main, other)) = found else {
+                println!("{name}: agrees on {cases} inputs");
+                continue;
+            };
+            ok = false;
+
+            let shrunk = shrink(&input, |input| pair.disagree(input, (&main, &other)));
+            let (main, other) = pair.solve(&shrunk).expect("the shrunk input disagrees");
+            let path = regressions(day).join(format!("{part}-{}-{seed}.txt", variant.name));
+            fs::create_dir_all(regressions(day))
+                .and_then(|()| fs::write(&path, &shrunk))
+                .map_err(|e| format!("couldn't write {}: {e}", path.display()))?;
+            println!(
+                "{name}: disagrees on seed {seed}, shrunk from {} to {} bytes and saved to {}",
+                input.len(),
+                shrunk.len(),
+                path.display()
+            );
+            println!("  {part}: {}", describe(&main));
+            println!("  {}: {}", variant.name, describe(&other));
+        }
+    }
+    Ok(ok)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shrink() {
+        // Keeps inputs with a line holding both an `a` and a `b`.
+        let keep = |input: &str| {
+            input
+                .lines()
+                .any(|line| line.contains('a') && line.contains('b'))
+        };
+        let input = "xyz\nqaxbq\nab\nzzz\n";
+        assert_eq!(shrink(input, keep), "ab");
+
+        let outcomes = (Ok("1".to_owned()), Err("panicked".to_owned()));
+        let same = (Ok("2".to_owned()), Err("panicked at lib.rs".to_owned()));
+        let other = (Ok("2".to_owned()), Ok("3".to_owned()));
+        assert!(same_kind((&outcomes.0, &outcomes.1), (&same.0, &same.1)));
+        assert!(!same_kind((&outcomes.0, &outcomes.1), (&other.0, &other.1)));
+    }
+
+    /// The inputs saved by `difftest` must be solved the same by every
+    /// variant, once fixed.
+    #[test]
+    fn test_regressions() {
+        for day in DAYS {
+            let Ok(entries) = fs::read_dir(regressions(day)) else {
+                continue;
+            };
+            let ctx = day.solution.context(false);
+            for entry in entries {
+                let path = entry.unwrap().path();
+                let input = fs::read_to_string(&path).unwrap();
+                for part in Part::ALL {
+                    for variant in day.solution.variants(part) {
+                        let pair = Pair {
+                            day,
+                            part,
+                            variant,
+                            ctx: &ctx,
+                        };
+                        if let Some((main, other)) = pair.solve(&input) {
+                            assert_eq!(
+                                main,
+                                other,
+                                "{day} {part} ({}) on {}",
+                                variant.name,
+                                path.display()
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,342 @@
+//! Solving each part in a child process, so a runaway part can be stopped
+//! when it takes too long or uses too much memory.
+//!
+//! The child is the runner itself, with the hidden `part` command. It prints
+//! how it went as JSON on the last line of its output.
+
+use std::{
+    env,
+    io::{self, Read, Write},
+    mem,
+    os::unix::process::{CommandExt, ExitStatusExt},
+    process::{Child, Command, ExitStatus, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use aoc::alloc::{self, Stats};
+use serde_json::{json, Value};
+
+use crate::{catch, checkpoints, progress, report::Stage, Day, Options};
+
+/// How long a part may take, unless given with `--timeout`.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_mins(1);
+/// How much memory a part may use in MiB, unless given with `--memory`.
+pub const DEFAULT_MEMORY: u64 = 4096;
+
+/// How often to check whether the child is done.
+const POLL: Duration = Duration::from_millis(5);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// The stage finished, with the answer if it's a part.
+    Solved(Option<String>),
+    /// The stage panicked, with the description from [`catch::catch`].
+    Panicked(String),
+    TimedOut,
+    OutOfMemory,
+}
+
+/// How solving a stage went.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    pub outcome: Outcome,
+    pub time: Duration,
+    /// What the stage allocated, if it was counted.
+    pub allocs: Option<Stats>,
+    /// The most memory the child had resident at once, in bytes (unlike the
+    /// heap peak of `allocs`, it includes the stack, the code and the
+    /// allocator's overhead). Only known for stages solved in a child.
+    pub resident: Option<u64>,
+}
+
+/// Solves the stage in this process, catching panics.
+pub fn solve_here(f: impl FnOnce() -> Option<String>) -> Run {
+    let start = Instant::now();
+    let (result, allocs) = alloc::measure(|| catch::catch(f));
+    let time = start.elapsed();
+    let outcome = match result {
+        Ok(answer) => Outcome::Solved(answer),
+        Err(panic) => Outcome::Panicked(panic),
+    };
+    Run {
+        outcome,
+        time,
+        allocs,
+        resident: None,
+    }
+}
+
+/// Solves the stage in this process, for the parent, printing how it went.
+pub fn solve_for_parent(f: impl FnOnce() -> Option<String>) {
+    let run = solve_here(f);
+    let mut line = match run.outcome {
+        Outcome::Solved(answer) => json!({ "outcome": "solved", "answer": answer }),
+        Outcome::Panicked(panic) => json!({ "outcome": "panicked", "panic": panic }),
+        Outcome::TimedOut | Outcome::OutOfMemory => unreachable!("only known to the parent"),
+    };
+    line["time_ns"] = json!(run.time.as_nanos());
+    line["allocs"] = json!(run.allocs.map(stats_to_json));
+    println!("\n{line}");
+}
+
+pub fn stats_to_json(stats: Stats) -> Value {
+    json!({
+        "allocations": stats.allocations,
+        "bytes": stats.bytes,
+        "heap_peak": stats.peak,
+    })
+}
+
+fn stats_from_json(value: &Value) -> Option<Stats> {
+    Some(Stats {
+        allocations: value["allocations"].as_u64()?,
+        bytes: value["bytes"].as_u64()?,
+        peak: value["heap_peak"].as_u64()?,
+    })
+}
+
+/// The memory limit of a child in bytes, or `None` if `mib` is too large.
+pub fn memory_limit(mib: u64) -> Option<u64> {
+    mib.checked_mul(1024 * 1024)
+}
+
+/// Solves the stage (on the real input or the named example) in a child
+/// process, within the time and memory limits of the options.
+pub fn solve_in_child(day: &Day, stage: Stage, input: &str, options: &Options) -> io::Result<Run> {
+    let timeout = options.timeout.unwrap_or(DEFAULT_TIMEOUT);
+    let memory = memory_limit(options.memory.unwrap_or(DEFAULT_MEMORY))
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "memory limit too large"))?;
+
+    let mut command = Command::new(env::current_exe()?);
+    command
+        .args(["part", "--year", &day.year.to_string()])
+        .args(["--day", &day.day.to_string()])
+        .args(["--part", stage.arg()])
+        .args(["--input", input])
+        .args(["--progress", &progress(options).to_string()])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    for (name, value) in &options.params {
+        command.args(["--param", &format!("{name}={value}")]);
+    }
+    if let Stage::Variant(_, name) = stage {
+        command.args(["--variant", name]);
+    }
+    if !checkpoints(options) {
+        command.arg("--no-checkpoint");
+    }
+    // SAFETY: `setrlimit` is async-signal-safe, and nothing is allocated
+    // between the fork and the exec.
+    unsafe {
+        command.pre_exec(move || {
+            let limit = libc::rlimit {
+                rlim_cur: memory,
+                rlim_max: memory,
+            };
+            if libc::setrlimit(libc::RLIMIT_AS, &raw const limit) == 0 {
+                Ok(())
+            } else {
+                Err(io::Error::last_os_error())
+            }
+        });
+    }
+
+    let start = Instant::now();
+    let mut child = command.spawn()?;
+    let mut stdout = child.stdout.take().expect("stdout is piped");
+    let stdout = thread::spawn(move || {
+        let mut out = String::new();
+        let _ = stdout.read_to_string(&mut out);
+        out
+    });
+    let stderr = child.stderr.take().expect("stderr is piped");
+    let stderr = thread::spawn(move || forward(stderr));
+
+    let mut timed_out = false;
+    let (status, usage) = loop {
+        if let Some(ended) = wait(&child, false)? {
+            break ended;
+        }
+        if start.elapsed() > timeout {
+            timed_out = true;
+            child.kill()?;
+            break wait(&child, true)?.expect("the child was waited for");
+        }
+        thread::sleep(POLL);
+    };
+    // Linux counts it in KiB.
+    let resident = u64::try_from(usage.ru_maxrss).ok().map(|kib| kib * 1024);
+    let elapsed = start.elapsed();
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+
+    if timed_out {
+        return Ok(Run {
+            outcome: Outcome::TimedOut,
+            time: elapsed,
+            allocs: None,
+            resident,
+        });
+    }
+    Ok(Run {
+        resident,
+        ..classify(status, &stdout, &stderr, elapsed)
+    })
+}
+
+/// Waits for the child to end (or only checks whether it has, unless
+/// `block`), with what it used. The standard library's `wait` doesn't return
+/// the usage.
+fn wait(child: &Child, block: bool) -> io::Result<Option<(ExitStatus, libc::rusage)>> {
+    let pid = libc::pid_t::try_from(child.id()).expect("pids fit in pid_t");
+    let flags = if block { 0 } else { libc::WNOHANG };
+    let mut status = 0;
+    // SAFETY: `rusage` is plain data, for which zeroes are valid.
+    let mut usage: libc::rusage = unsafe { mem::zeroed() };
+    loop {
+        // SAFETY: the pointers are to valid locals.
+        match unsafe { libc::wait4(pid, &raw mut status, flags, &raw mut usage) } {
+            0 => return Ok(None),
+            -1 => {
+                let error = io::Error::last_os_error();
+                if error.kind() != io::ErrorKind::Interrupted {
+                    return Err(error);
+                }
+            }
+            _ => return Ok(Some((ExitStatus::from_raw(status), usage))),
+        }
+    }
+}
+
+/// Passes on what the child writes to stderr as it comes, e.g. its progress,
+/// and returns all of it. Running out of memory is reported on its own, so
+/// its message (and the backtrace after it) is left out.
+fn forward(mut pipe: impl Read) -> String {
+    let mut all = Vec::new();
+    let mut buf = [0; 4096];
+    let mut out_of_memory = false;
+    loop {
+        let chunk = match pipe.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => &buf[..n],
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(_) => break,
+        };
+        out_of_memory |= String::from_utf8_lossy(chunk).contains("memory allocation of");
+        if !out_of_memory {
+            let _ = io::stderr().write_all(chunk);
+        }
+        all.extend_from_slice(chunk);
+    }
+    String::from_utf8_lossy(&all).into_owned()
+}
+
+/// Makes sense of how the child ended, preferring the time it measured
+/// itself to the time it took from the outside.
+fn classify(status: ExitStatus, stdout: &str, stderr: &str, elapsed: Duration) -> Run {
+    let reported = stdout
+        .lines()
+        .last()
+        .and_then(|line| serde_json::from_str::<Value>(line).ok())
+        .unwrap_or_default();
+    let outcome = match reported["outcome"].as_str() {
+        Some("solved") => Some(Outcome::Solved(
+            reported["answer"].as_str().map(str::to_owned),
+        )),
+        Some("panicked") => reported["panic"]
+            .as_str()
+            .map(|panic| Outcome::Panicked(panic.to_owned())),
+        _ => None,
+    };
+    let time = reported["time_ns"].as_u64().map(Duration::from_nanos);
+    if let (Some(outcome), Some(time)) = (outcome, time) {
+        let allocs = stats_from_json(&reported["allocs"]);
+        return Run {
+            outcome,
+            time,
+            allocs,
+            resident: None,
+        };
+    }
+
+    // Past the limit allocations fail, which aborts the child with this
+    // message (or gets it killed, if it was still aborting when time ran
+    // out). Being killed without it is something else, e.g. the kernel's OOM
+    // killer making room for another process, or a user.
+    let killed = matches!(status.signal(), Some(libc::SIGABRT | libc::SIGKILL));
+    let outcome = if killed && stderr.contains("memory allocation of") {
+        Outcome::OutOfMemory
+    } else {
+        Outcome::Panicked(format!("child {status}"))
+    };
+    Run {
+        outcome,
+        time: elapsed,
+        allocs: None,
+        resident: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        let exited = ExitStatus::from_raw(0);
+        let aborted = ExitStatus::from_raw(libc::SIGABRT);
+        let killed = ExitStatus::from_raw(libc::SIGKILL);
+        let elapsed = Duration::from_millis(3);
+        let outcome = |status, stdout, stderr| classify(status, stdout, stderr, elapsed).outcome;
+
+        let stdout = r#"debug
+{"outcome":"solved","answer":"42","time_ns":7,"allocs":{"allocations":1,"bytes":8,"heap_peak":8}}
+"#;
+        assert_eq!(
+            classify(exited, stdout, "", elapsed),
+            Run {
+                outcome: Outcome::Solved(Some("42".to_owned())),
+                time: Duration::from_nanos(7),
+                allocs: Some(Stats {
+                    allocations: 1,
+                    bytes: 8,
+                    peak: 8
+                }),
+                resident: None,
+            }
+        );
+        assert_eq!(
+            outcome(
+                exited,
+                r#"{"outcome":"solved","answer":null,"time_ns":7,"allocs":null}"#,
+                ""
+            ),
+            Outcome::Solved(None)
+        );
+        assert_eq!(
+            outcome(
+                exited,
+                r#"{"outcome":"panicked","panic":"panicked: oops","time_ns":7}"#,
+                ""
+            ),
+            Outcome::Panicked("panicked: oops".to_owned())
+        );
+        let message = "memory allocation of 8589934592 bytes failed\n";
+        assert_eq!(outcome(aborted, "", message), Outcome::OutOfMemory);
+        assert_eq!(outcome(killed, "", message), Outcome::OutOfMemory);
+        assert_eq!(
+            outcome(killed, "", ""),
+            Outcome::Panicked("child signal: 9 (SIGKILL)".to_owned())
+        );
+        assert_eq!(
+            outcome(aborted, "", "assertion failed"),
+            Outcome::Panicked("child signal: 6 (SIGABRT)".to_owned())
+        );
+        assert_eq!(
+            outcome(ExitStatus::from_raw(1 << 8), "", ""),
+            Outcome::Panicked("child exit status: 1".to_owned())
+        );
+    }
+}
@@ -0,0 +1,86 @@
+//! The days the runner knows about, shared by the runner and the benchmarks.
+
+mod days;
+
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use aoc::Solution;
+
+pub use days::DAYS;
+
+#[derive(Debug)]
+pub struct Day {
+    /// The last two digits of the year, as in the directory names.
+    pub year: u16,
+    pub day: u8,
+    pub solution: &'static Solution,
+}
+
+impl Day {
+    #[must_use]
+    pub const fn new(year: u16, day: u8, solution: &'static Solution) -> Self {
+        Day {
+            year,
+            day,
+            solution,
+        }
+    }
+
+    /// The directory with the day's code, input and examples.
+    #[must_use]
+    pub fn dir(&self) -> PathBuf {
+        root().join(self.to_string())
+    }
+}
+
+impl fmt::Display for Day {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/day{:02}", self.year, self.day)
+    }
+}
+
+/// The root of the workspace, where `cargo` is run from.
+///
+/// # Panics
+///
+/// If the runner was moved out of the workspace.
+#[must_use]
+pub fn root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("runner is in the workspace")
+}
+
+/// Finds the given day, in the given year or else in the most recent year that
+/// has it.
+///
+/// # Errors
+///
+/// If there's no such day.
+pub fn find(days: &[Day], year: Option<u16>, day: u8) -> Result<&Day, String> {
+    days.iter()
+        .filter(|d| d.day == day && year.is_none_or(|year| d.year == year))
+        .max_by_key(|d| d.year)
+        .ok_or_else(|| match year {
+            Some(year) => format!("no solution for day {day} of {year}"),
+            None => format!("no solution for day {day}"),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        let day = |year, day| find(DAYS, year, day).map(ToString::to_string);
+        assert_eq!(day(None, 4), Ok("23/day04".to_owned()));
+        assert_eq!(day(None, 3), Ok("24/day03".to_owned()));
+        assert_eq!(day(Some(23), 3), Ok("23/day03".to_owned()));
+        assert!(day(Some(24), 4).is_err());
+        assert!(day(None, 26).is_err());
+    }
+}
@@ -0,0 +1,764 @@
+//! Runs the solutions of every day, e.g. `cargo aoc --day 4 --example`.
+
+mod bench;
+mod catch;
+mod difftest;
+mod isolate;
+mod report;
+mod watch;
+
+use std::{
+    env,
+    fmt::Write as _,
+    io::{self, IsTerminal},
+    iter, process,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use aoc::{Checkpoint, Context, Part, Progress, ProgressMode, Rng, Solution};
+use rayon::prelude::*;
+use runner::{find, root, Day, DAYS};
+
+use isolate::{Outcome, Run};
+use report::{DayRun, Format, Report, Reporter, Stage, Status};
+
+// Only counts anything with `--allocs`, and costs little otherwise.
+#[global_allocator]
+static ALLOCATOR: aoc::alloc::Counting = aoc::alloc::Counting;
+
+#[derive(Debug, Default, PartialEq)]
+enum Command {
+    /// Solve the day once.
+    #[default]
+    Run,
+    /// Solve the day again whenever it changes.
+    Watch,
+    /// Time the parts, and compare with the history (see [`bench`]).
+    Bench,
+    /// Solve a single part, for a parent runner (see [`isolate`]).
+    Part,
+    /// Print a random input for the day.
+    Generate,
+    /// Compare the variants with their parts on generated inputs (see
+    /// [`difftest`]).
+    Difftest,
+}
+
+/// The size of the generated inputs, unless given with `--size`.
+const DEFAULT_SIZE: usize = 100;
+
+// The flags are independent of each other.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Default, PartialEq)]
+struct Options {
+    command: Command,
+    year: Option<u16>,
+    day: Option<u8>,
+    /// Solve every day, instead of the one given with `--day`.
+    all: bool,
+    /// With `--all`, solve the days one at a time, so they don't compete for
+    /// the CPU and their timings can be compared.
+    serial: bool,
+    /// Solve the examples instead of the real input.
+    example: bool,
+    format: Format,
+    /// Parameter overrides, from `--param <name>=<value>`.
+    params: Vec<(String, String)>,
+    /// How long a part may take before it's stopped.
+    timeout: Option<Duration>,
+    /// How much memory a part may use, in MiB.
+    memory: Option<u64>,
+    /// Solve the parts in the runner's process, without any limits.
+    in_process: bool,
+    /// How to show the progress of long-running parts, instead of deciding
+    /// with [`progress`].
+    progress: Option<ProgressMode>,
+    /// Neither resume nor save the searches of the parts (see [`checkpoints`]).
+    no_checkpoint: bool,
+    /// Solve the variants of the parts too, checking them against the parts.
+    variants: bool,
+    /// Show what each stage allocates and its resident peak, and solve the
+    /// parsing on its own too.
+    allocs: bool,
+    /// With `bench`, how many times to solve each part.
+    samples: Option<usize>,
+    /// With `bench`, how much slower than the baseline a part may get, in
+    /// percent.
+    threshold: Option<f64>,
+    /// With `part`, the stage to solve.
+    part: Option<Stage>,
+    /// With `part`, the variant of the part to solve instead.
+    variant: Option<String>,
+    /// With `part`, `input` or the name of the example to solve.
+    input: Option<String>,
+    /// With `generate`, the seed of the input (0 by default). With
+    /// `difftest`, the seed of the first input.
+    seed: Option<u64>,
+    /// With `generate`, the size of the input, as the day understands it.
+    /// With `difftest`, the size of the largest input.
+    size: Option<usize>,
+    /// With `difftest`, how many inputs to generate.
+    cases: Option<usize>,
+}
+
+impl Options {
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut options = Options::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_owned())),
+                _ => (arg.as_str(), None),
+            };
+            let mut value = || {
+                inline
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| format!("missing value for `{flag}`"))
+            };
+            match flag {
+                "watch" if options == Options::default() => options.command = Command::Watch,
+                "part" if options == Options::default() => options.command = Command::Part,
+                "bench" if options == Options::default() => options.command = Command::Bench,
+                "generate" if options == Options::default() => options.command = Command::Generate,
+                "difftest" if options == Options::default() => options.command = Command::Difftest,
+                "--year" => options.year = Some(number(flag, &value()?)?),
+                "--day" => options.day = Some(number(flag, &value()?)?),
+                "--all" => options.all = true,
+                "--serial" => options.serial = true,
+                "--example" => options.example = true,
+                "--format" => options.format = value()?.parse()?,
+                "--timeout" => {
+                    let seconds = number(flag, &value()?)?;
+                    let timeout = Duration::try_from_secs_f64(seconds)
+                        .map_err(|e| format!("invalid timeout: {e}"))?;
+                    options.timeout = Some(timeout);
+                }
+                "--memory" => {
+                    let mib = number(flag, &value()?)?;
+                    isolate::memory_limit(mib)
+                        .ok_or_else(|| format!("`--memory` is too large, got {mib} MiB"))?;
+                    options.memory = Some(mib);
+                }
+                "--in-process" => options.in_process = true,
+                "--allocs" => options.allocs = true,
+                "--variants" => options.variants = true,
+                "--variant" => options.variant = Some(value()?),
+                "--no-checkpoint" => options.no_checkpoint = true,
+                "--progress" => options.progress = Some(value()?.parse()?),
+                "--samples" => options.samples = Some(number(flag, &value()?)?),
+                "--threshold" => options.threshold = Some(number(flag, &value()?)?),
+                "--part" => options.part = Some(value()?.parse()?),
+                "--input" => options.input = Some(value()?),
+                "--seed" => options.seed = Some(number(flag, &value()?)?),
+                "--size" => options.size = Some(number(flag, &value()?)?),
+                "--cases" => options.cases = Some(number(flag, &value()?)?),
+                "--param" => {
+                    let param = value()?;
+                    let (name, value) = param
+                        .split_once('=')
+                        .ok_or_else(|| format!("expected `<name>=<value>`, got `{param}`"))?;
+                    options.params.push((name.to_owned(), value.to_owned()));
+                }
+                _ => return Err(format!("unexpected argument `{arg}`")),
+            }
+        }
+        Ok(options)
+    }
+}
+
+fn number<T: FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("expected a number for `{flag}`, got `{value}`"))
+}
+
+/// The context to solve the real input (or an example) with the parameters
+/// of the options, which the day must have declared (unless it takes extra
+/// ones).
+fn context(solution: &Solution, options: &Options, is_example: bool) -> Result<Context, String> {
+    let mut ctx = solution.context(is_example);
+    for (name, value) in &options.params {
+        ctx.set(name, value)?;
+    }
+    Ok(ctx)
+}
+
+/// How to show the progress of long-running parts: bars on a terminal, and
+/// log lines elsewhere or when several days are solved at once. There's none
+/// with JSON output or while benchmarking.
+fn progress(options: &Options) -> ProgressMode {
+    if let Some(mode) = options.progress {
+        mode
+    } else if options.format == Format::Json || options.command == Command::Bench {
+        ProgressMode::Silent
+    } else if options.all || !io::stderr().is_terminal() {
+        ProgressMode::Log
+    } else {
+        ProgressMode::Bars
+    }
+}
+
+/// Whether long searches on the real input save how far they got, and resume
+/// from there. Not while benchmarking, or with other parameters.
+fn checkpoints(options: &Options) -> bool {
+    !options.no_checkpoint && options.command != Command::Bench && options.params.is_empty()
+}
+
+/// The context to solve the stage with, reporting its progress and saving its
+/// checkpoints as the options say. It's built before the stage is measured.
+fn stage_context(day: &Day, stage: Stage, ctx: &Context, options: &Options) -> Context {
+    let mut ctx = ctx.clone();
+    ctx.progress = Progress::new(progress(options), format!("{day} {stage}"));
+    if checkpoints(options) && !ctx.is_example {
+        let mut scope = format!("{day} {stage}");
+        for (name, value) in ctx.params() {
+            let _ = write!(scope, " {name}={value}");
+        }
+        let version = day.solution.checkpoint_version;
+        let stage = stage.to_string().replace(' ', "-");
+        let name = format!("{}-day{:02}-{stage}", day.year, day.day);
+        let path = root().join("target/checkpoints").join(name);
+        ctx.checkpoint = Checkpoint::new(path, version, scope);
+    }
+    ctx
+}
+
+/// Solves the stage on the input, returning the answer if it's a part.
+fn solve_stage(day: &Day, stage: Stage, input: &str, ctx: &Context) -> Option<String> {
+    let solve = match stage {
+        Stage::Parse => {
+            if let Some(parse) = day.solution.parse {
+                parse(input);
+            }
+            return None;
+        }
+        Stage::Part(part) => day.solution.part(part),
+        Stage::Variant(part, name) => {
+            let variant = day.solution.variant(part, name);
+            variant.expect("the variant is registered").solve
+        }
+    };
+    Some(solve(input, ctx))
+}
+
+/// Solves the given stages on the input, comparing the answers with the
+/// expected ones if known, and the answers of variants with the ones of their
+/// parts. Returns whether no stage failed.
+fn solve(
+    report: &mut dyn FnMut(Report),
+    options: &Options,
+    day: &Day,
+    name: &str,
+    input: &str,
+    ctx: &Context,
+    stages: &[(Stage, Option<&'static str>)],
+) -> bool {
+    let mut ok = true;
+    // The answers of the parts and how long they took, for their variants.
+    let mut solved = Vec::new();
+    for &(stage, expected) in stages {
+        let mut result = Report {
+            day: day.to_string(),
+            input: name.to_owned(),
+            stage,
+            status: Status::Skipped,
+            answer: None,
+            expected,
+            time: None,
+            allocs: None,
+            resident: None,
+            reference: None,
+            reasons: Vec::new(),
+        };
+        let broken = match stage.part() {
+            Some(part) => day.solution.broken_invariants(part, input),
+            None => Vec::new(),
+        };
+        if broken.is_empty() {
+            let run = if options.in_process {
+                let ctx = stage_context(day, stage, ctx, options);
+                isolate::solve_here(|| solve_stage(day, stage, input, &ctx))
+            } else {
+                isolate::solve_in_child(day, stage, name, options).unwrap_or_else(|e| Run {
+                    outcome: Outcome::Panicked(format!("couldn't start the child: {e}")),
+                    time: Duration::ZERO,
+                    allocs: None,
+                    resident: None,
+                })
+            };
+            result.time = Some(run.time);
+            result.allocs = run.allocs;
+            result.resident = run.resident;
+            match run.outcome {
+                Outcome::Solved(answer) => {
+                    result.status = match (&answer, expected) {
+                        (None, _) => Status::Ok,
+                        (Some(answer), Some(expected)) if answer == expected => Status::Ok,
+                        (Some(_), Some(_)) => Status::Wrong,
+                        (Some(_), None) => Status::Unverified,
+                    };
+                    result.answer = answer;
+                }
+                Outcome::Panicked(panic) => {
+                    result.status = Status::Failed;
+                    result.reasons.push(panic);
+                }
+                Outcome::TimedOut => {
+                    let timeout = options.timeout.unwrap_or(isolate::DEFAULT_TIMEOUT);
+                    result.status = Status::Timeout;
+                    result.reasons.push(format!("took longer than {timeout:?}"));
+                }
+                Outcome::OutOfMemory => {
+                    let memory = options.memory.unwrap_or(isolate::DEFAULT_MEMORY);
+                    result.status = Status::Oom;
+                    result.reasons.push(format!("used more than {memory} MiB"));
+                }
+            }
+        } else {
+            result.reasons = broken
+                .into_iter()
+                .map(|(invariant, reason)| {
+                    format!(
+                        "input breaks assumption `{}`: {reason}",
+                        invariant.description
+                    )
+                })
+                .collect();
+        }
+        match (stage, &result.answer, result.time) {
+            (Stage::Part(part), Some(answer), Some(time)) => {
+                solved.push((part, answer.clone(), time));
+            }
+            (Stage::Variant(part, _), Some(answer), _) => {
+                if let Some((_, main, time)) = solved.iter().find(|(p, ..)| *p == part) {
+                    result.reference = Some(*time);
+                    if answer != main {
+                        result.status = Status::Wrong;
+                        result
+                            .reasons
+                            .push(format!("disagrees with {part}: {main}"));
+                    }
+                }
+            }
+            _ => {}
+        }
+        ok &= !result.status.is_failure();
+        report(result);
+    }
+    ok
+}
+
+/// Solves the day, or its examples with `--example`, passing on the report of
+/// every stage as soon as it's solved.
+fn solve_day(day: &Day, options: &Options, report: &mut dyn FnMut(Report)) {
+    let solution = day.solution;
+    // The known answers don't hold with other parameters.
+    let expected = |answer: Option<&'static str>| answer.filter(|_| options.params.is_empty());
+
+    // The parsing is only solved on its own to see what it allocates.
+    let parse = (options.allocs && solution.parse.is_some()).then_some((Stage::Parse, None));
+    // Each part is followed by its variants, to check them against it.
+    let with_variants = |part, expected| {
+        let variants = solution
+            .variants(part)
+            .filter(|_| options.variants)
+            .map(move |variant| (Stage::Variant(part, variant.name), expected));
+        iter::once((Stage::Part(part), expected)).chain(variants)
+    };
+
+    if !options.example {
+        let parts = Part::ALL
+            .into_iter()
+            .flat_map(|part| with_variants(part, expected(solution.answer(part))));
+        let stages: Vec<_> = parse.into_iter().chain(parts).collect();
+        let ctx = context(solution, options, false).expect("the parameters are checked");
+        solve(report, options, day, "input", solution.input, &ctx, &stages);
+        return;
+    }
+    let ctx = context(solution, options, true).expect("the parameters are checked");
+    for (i, example) in solution.examples.iter().enumerate() {
+        let name = format!("example{}", i + 1);
+        let parts = Part::ALL
+            .into_iter()
+            .filter(|&part| example.illustrates(part))
+            .flat_map(|part| with_variants(part, expected(example.answer(part))));
+        let stages: Vec<_> = parse.into_iter().chain(parts).collect();
+        // The later examples are usually built upon the earlier ones.
+        if !solve(report, options, day, &name, example.input, &ctx, &stages) {
+            break;
+        }
+    }
+}
+
+/// Solves every day (of `--year`, if given), in parallel unless `--serial`.
+/// A day that panics doesn't stop the others.
+fn run_all(options: &Options) -> bool {
+    let start = Instant::now();
+    let solve = |day: &Day| {
+        let start = Instant::now();
+        let mut reports = Vec::new();
+        // The parts' panics are caught on their own, this is for the rest.
+        let result = catch::catch(|| {
+            solve_day(day, options, &mut |report| reports.push(report));
+        });
+        let run = DayRun {
+            day: day.to_string(),
+            time: start.elapsed(),
+            panic: result.err(),
+        };
+        (run, reports)
+    };
+    let selected = |day: &&Day| options.year.is_none_or(|year| day.year == year);
+    let results: Vec<_> = if options.serial {
+        DAYS.iter().filter(selected).map(solve).collect()
+    } else {
+        DAYS.par_iter().filter(selected).map(solve).collect()
+    };
+
+    let mut reporter = Reporter::new(options.format, options.allocs);
+    let mut runs = Vec::new();
+    for (run, reports) in results {
+        reporter.header(&run.day);
+        for report in reports {
+            reporter.add(report);
+        }
+        runs.push(run);
+    }
+    reporter.summary(&runs, start.elapsed());
+    let panicked = runs.iter().any(|run| run.panic.is_some());
+    reporter.finish() && !panicked
+}
+
+/// Solves the stage of `--part` on the input of `--input`, for a parent
+/// runner.
+fn run_part(options: &Options) -> Result<bool, String> {
+    let day = options.day.ok_or("missing `--day`")?;
+    let day = find(DAYS, options.year, day)?;
+    let mut stage = options.part.ok_or("missing `--part`")?;
+    let solution = day.solution;
+    if let Some(name) = &options.variant {
+        let part = stage.part().ok_or("`--variant` needs a part")?;
+        let variant = solution
+            .variant(part, name)
+            .ok_or_else(|| format!("{day} has no variant `{name}` of {part}"))?;
+        stage = Stage::Variant(part, variant.name);
+    }
+    let (input, is_example) = match options.input.as_deref().unwrap_or("input") {
+        "input" => (solution.input, false),
+        name => {
+            let example = name
+                .strip_prefix("example")
+                .and_then(|i| i.parse::<usize>().ok())
+                .and_then(|i| solution.examples.get(i.checked_sub(1)?))
+                .ok_or_else(|| format!("{day} has no input named `{name}`"))?;
+            (example.input, true)
+        }
+    };
+    let ctx = stage_context(
+        day,
+        stage,
+        &context(solution, options, is_example)?,
+        options,
+    );
+    isolate::solve_for_parent(|| solve_stage(day, stage, input, &ctx));
+    Ok(true)
+}
+
+/// Prints the input the day generates for `--seed` and `--size`.
+fn run_generate(options: &Options) -> Result<bool, String> {
+    let day = options.day.ok_or("missing `--day`")?;
+    let day = find(DAYS, options.year, day)?;
+    let generate = day
+        .solution
+        .generate
+        .ok_or_else(|| format!("{day} has no generator"))?;
+    let size = options.size.unwrap_or(DEFAULT_SIZE);
+    if size == 0 {
+        return Err("`--size` must be at least 1".to_owned());
+    }
+    let mut rng = Rng::new(options.seed.unwrap_or(0));
+    print!("{}", generate(&mut rng, size));
+    Ok(true)
+}
+
+fn run(options: &Options) -> Result<bool, String> {
+    if options.command == Command::Part {
+        return run_part(options);
+    }
+    if options.command == Command::Bench {
+        return bench::bench(options);
+    }
+    if options.command == Command::Generate {
+        return run_generate(options);
+    }
+    if options.command == Command::Difftest {
+        return difftest::difftest(options);
+    }
+    if options.all {
+        if options.day.is_some() || options.command == Command::Watch {
+            return Err("`--all` only solves every day once".to_owned());
+        }
+        if !options.params.is_empty() {
+            return Err("`--param` only applies to a single `--day`".to_owned());
+        }
+        // The counters are shared by the whole process.
+        if options.allocs && options.in_process && !options.serial {
+            return Err("`--allocs` with `--in-process` needs `--serial`".to_owned());
+        }
+        return Ok(run_all(options));
+    }
+    let day = options.day.ok_or("missing `--day` or `--all`")?;
+    let day = find(DAYS, options.year, day)?;
+    context(day.solution, options, false).map_err(|e| format!("{day}: {e}"))?;
+    if options.command == Command::Watch {
+        return watch::watch(day, options).map(|()| true);
+    }
+    if options.example && day.solution.examples.is_empty() {
+        return Err(format!("{day} has no examples"));
+    }
+    let mut reporter = Reporter::new(options.format, options.allocs);
+    solve_day(day, options, &mut |report| reporter.add(report));
+    Ok(reporter.finish())
+}
+
+fn main() {
+    catch::install_hook();
+    let mut args = env::args();
+    let program = args.next().unwrap_or_default();
+    let result = Options::parse(args).and_then(|options| run(&options));
+    match result {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(error) => {
+            eprintln!("error: {error}");
+            eprintln!(
+                "usage: {program} [watch] --day <n> [--year <yy>] [--example] [--format text|json] [--param <name>=<value>]..."
+            );
+            eprintln!("       [--timeout <seconds>] [--memory <MiB>] [--in-process] [--allocs] [--variants] [--progress bars|log|silent] [--no-checkpoint]");
+            eprintln!(
+                "       {program} --all [--serial] [--year <yy>] [--example] [--format text|json]"
+            );
+            eprintln!(
+                "       {program} bench (--day <n> | --all) [--year <yy>] [--samples <n>] [--threshold <percent>]"
+            );
+            eprintln!(
+                "       {program} generate --day <n> [--year <yy>] [--seed <n>] [--size <n>]"
+            );
+            eprintln!(
+                "       {program} difftest --day <n> [--year <yy>] [--seed <n>] [--size <n>] [--cases <n>] [--param <name>=<value>]..."
+            );
+            process::exit(2);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress() {
+        let options = |args: &[&str]| Options::parse(args.iter().map(|&arg| arg.to_owned()));
+        let progress = |args| progress(&options(args).unwrap());
+        assert_eq!(progress(&["--all"]), ProgressMode::Log);
+        assert_eq!(progress(&["--format", "json"]), ProgressMode::Silent);
+        assert_eq!(progress(&["bench", "--all"]), ProgressMode::Silent);
+        assert_eq!(
+            progress(&["--format", "json", "--progress", "bars"]),
+            ProgressMode::Bars
+        );
+        assert!(options(&["--progress", "loud"]).is_err());
+    }
+
+    #[test]
+    fn test_params() {
+        let options = |args: &[&str]| Options::parse(args.iter().map(|&arg| arg.to_owned()));
+        let run = |args| run(&options(args).unwrap());
+        assert_eq!(
+            run(&["--day", "1", "--year", "23", "--param", "gren=5"]),
+            Err("23/day01: unknown parameter `gren`, there are none".to_owned())
+        );
+        assert_eq!(
+            run(&["--all", "--param", "red=5"]),
+            Err("`--param` only applies to a single `--day`".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_solve_after_panic() {
+        static SOLUTION: Solution =
+            Solution::new("", |_, _| panic!("bug"), |input, _| input.len().to_string());
+        catch::install_hook();
+        let day = Day::new(23, 1, &SOLUTION);
+        let mut reports = Vec::new();
+        let stages = Part::ALL.map(|part| (Stage::Part(part), None));
+        let options = Options {
+            in_process: true,
+            ..Options::default()
+        };
+        let ok = solve(
+            &mut |report| reports.push(report),
+            &options,
+            &day,
+            "input",
+            "abc",
+            &SOLUTION.context(false),
+            &stages,
+        );
+        assert!(!ok);
+        assert_eq!(reports[0].status, Status::Failed);
+        assert!(reports[0].reasons[0].ends_with(": bug"));
+        assert_eq!(reports[1].status, Status::Unverified);
+        assert_eq!(reports[1].answer.as_deref(), Some("3"));
+    }
+
+    #[test]
+    fn test_variants() {
+        static SOLUTION: Solution =
+            Solution::new("", |_, _| String::new(), |input, _| input.len().to_string())
+                .with_variants(&[
+                    aoc::Variant::new("same", Part::Two, |input, _| input.len().to_string()),
+                    aoc::Variant::new("off", Part::Two, |input, _| (input.len() + 1).to_string()),
+                ]);
+        let day = Day::new(23, 1, &SOLUTION);
+        let mut reports = Vec::new();
+        let stages = [
+            (Stage::Part(Part::Two), None),
+            (Stage::Variant(Part::Two, "same"), None),
+            (Stage::Variant(Part::Two, "off"), None),
+        ];
+        let options = Options {
+            in_process: true,
+            ..Options::default()
+        };
+        let ok = solve(
+            &mut |report| reports.push(report),
+            &options,
+            &day,
+            "input",
+            "abc",
+            &SOLUTION.context(false),
+            &stages,
+        );
+        assert!(!ok);
+        let statuses: Vec<_> = reports.iter().map(|report| report.status).collect();
+        assert_eq!(
+            statuses,
+            [Status::Unverified, Status::Unverified, Status::Wrong]
+        );
+        assert_eq!(reports[1].reference, reports[0].time);
+        assert_eq!(reports[2].reasons, ["disagrees with part2: 3"]);
+    }
+
+    #[test]
+    fn test_options() {
+        let parse = |args: &[&str]| Options::parse(args.iter().map(|&arg| arg.to_owned()));
+
+        let options = parse(&[
+            "watch",
+            "--day",
+            "4",
+            "--year=23",
+            "--example",
+            "--format",
+            "json",
+            "--param",
+            "red=20",
+            "--param=violet=3",
+        ])
+        .unwrap();
+        assert_eq!(
+            options,
+            Options {
+                command: Command::Watch,
+                year: Some(23),
+                day: Some(4),
+                example: true,
+                format: Format::Json,
+                params: vec![
+                    ("red".to_owned(), "20".to_owned()),
+                    ("violet".to_owned(), "3".to_owned())
+                ],
+                ..Options::default()
+            }
+        );
+        assert_eq!(
+            parse(&["--all", "--serial"]),
+            Ok(Options {
+                all: true,
+                serial: true,
+                ..Options::default()
+            })
+        );
+
+        assert_eq!(
+            parse(&[
+                "part",
+                "--part",
+                "2",
+                "--input",
+                "example1",
+                "--timeout=0.5"
+            ]),
+            Ok(Options {
+                command: Command::Part,
+                part: Some(Stage::Part(Part::Two)),
+                input: Some("example1".to_owned()),
+                timeout: Some(Duration::from_millis(500)),
+                ..Options::default()
+            })
+        );
+
+        assert_eq!(
+            parse(&["bench", "--all", "--samples", "5", "--threshold=2.5"]),
+            Ok(Options {
+                command: Command::Bench,
+                all: true,
+                samples: Some(5),
+                threshold: Some(2.5),
+                ..Options::default()
+            })
+        );
+
+        assert_eq!(
+            parse(&["generate", "--day", "5", "--seed", "7", "--size=20"]),
+            Ok(Options {
+                command: Command::Generate,
+                day: Some(5),
+                seed: Some(7),
+                size: Some(20),
+                ..Options::default()
+            })
+        );
+
+        assert_eq!(
+            parse(&["difftest", "--day", "1", "--cases", "500"]),
+            Ok(Options {
+                command: Command::Difftest,
+                day: Some(1),
+                cases: Some(500),
+                ..Options::default()
+            })
+        );
+
+        assert!(parse(&["--day"]).is_err());
+        assert!(parse(&["--part", "3"]).is_err());
+        assert!(parse(&["--timeout", "-1"]).is_err());
+        assert_eq!(
+            parse(&["--memory", "17592186044415"]).map(|o| o.memory),
+            Ok(Some((1 << 44) - 1))
+        );
+        assert_eq!(
+            parse(&["--memory", "17592186044416"]),
+            Err("`--memory` is too large, got 17592186044416 MiB".to_owned())
+        );
+        assert!(parse(&["--day", "four"]).is_err());
+        assert!(parse(&["--param"]).is_err());
+        assert!(parse(&["--param", "red"]).is_err());
+        assert!(parse(&["--format", "yaml"]).is_err());
+        assert!(parse(&["--bogus"]).is_err());
+        assert!(parse(&["--day", "4", "watch"]).is_err());
+    }
+}
@@ -0,0 +1,387 @@
+//! What came out of solving each part, and how it's shown.
+
+use std::{fmt, str::FromStr, time::Duration};
+
+use aoc::{alloc::Stats, Part};
+use serde_json::{json, Value};
+
+use crate::isolate::stats_to_json;
+
+/// What is solved: a part, one of its variants, or only the parsing of the
+/// input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    Parse,
+    Part(Part),
+    /// The variant of the part with the given name.
+    Variant(Part, &'static str),
+}
+
+impl Stage {
+    /// The value of `--part` for the stage, along with `--variant` for
+    /// variants.
+    pub fn arg(self) -> &'static str {
+        match self {
+            Stage::Parse => "parse",
+            Stage::Part(Part::One) | Stage::Variant(Part::One, _) => "1",
+            Stage::Part(Part::Two) | Stage::Variant(Part::Two, _) => "2",
+        }
+    }
+
+    pub fn part(self) -> Option<Part> {
+        match self {
+            Stage::Parse => None,
+            Stage::Part(part) | Stage::Variant(part, _) => Some(part),
+        }
+    }
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stage::Parse => f.write_str("parse"),
+            Stage::Part(part) => part.fmt(f),
+            Stage::Variant(part, name) => write!(f, "{part} {name}"),
+        }
+    }
+}
+
+impl FromStr for Stage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Stage::Parse),
+            "1" => Ok(Stage::Part(Part::One)),
+            "2" => Ok(Stage::Part(Part::Two)),
+            _ => Err(format!("unknown part `{s}`, expected 1, 2 or `parse`")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// The answer is the expected one.
+    Ok,
+    /// The answer isn't the expected one.
+    Wrong,
+    /// There's no expected answer to compare with yet.
+    Unverified,
+    /// The input breaks assumptions of the part, so it wasn't solved.
+    Skipped,
+    /// The part panicked.
+    Failed,
+    /// The part took too long, and was stopped.
+    Timeout,
+    /// The part used too much memory.
+    Oom,
+}
+
+impl Status {
+    pub fn is_failure(self) -> bool {
+        !matches!(self, Status::Ok | Status::Unverified)
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Status::Ok => "ok",
+            Status::Wrong => "wrong",
+            Status::Unverified => "unverified",
+            Status::Skipped => "skipped",
+            Status::Failed => "failed",
+            Status::Timeout => "timeout",
+            Status::Oom => "oom",
+        })
+    }
+}
+
+/// The outcome of solving one stage on one input.
+#[derive(Debug, Clone)]
+pub struct Report {
+    /// The day, as in [`crate::Day`]'s `Display`.
+    pub day: String,
+    /// `input`, or `exampleN` for the examples.
+    pub input: String,
+    pub stage: Stage,
+    pub status: Status,
+    pub answer: Option<String>,
+    pub expected: Option<&'static str>,
+    pub time: Option<Duration>,
+    pub allocs: Option<Stats>,
+    /// The resident peak of the child that solved the stage, in bytes.
+    pub resident: Option<u64>,
+    /// For variants, the time of the main part, to compare with.
+    pub reference: Option<Duration>,
+    /// Why the part wasn't solved, or the panic that stopped it.
+    pub reasons: Vec<String>,
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.5 KiB`.
+fn bytes(n: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if n < 1024 {
+        return format!("{n} B");
+    }
+    let mut unit = 0;
+    let mut scaled = n;
+    while scaled >= 1024 * 1024 && unit < UNITS.len() - 1 {
+        scaled /= 1024;
+        unit += 1;
+    }
+    let tenths = scaled * 10 / 1024;
+    format!("{}.{} {}", tenths / 10, tenths % 10, UNITS[unit])
+}
+
+impl Report {
+    fn print_text(&self, show_allocs: bool) {
+        let label = match self.input.as_str() {
+            "input" => String::new(),
+            input => format!("{input} "),
+        };
+        let stage = self.stage;
+        let mut details = Vec::new();
+        if let Some(time) = self.time {
+            details.push(format!("{time:.1?}"));
+            if let (Some(reference), Some(part)) = (self.reference, stage.part()) {
+                let ratio = time.as_secs_f64() / reference.as_secs_f64();
+                details.push(format!("{ratio:.2}x {part}"));
+            }
+        }
+        if let Some(allocs) = self.allocs.filter(|_| show_allocs) {
+            details.push(format!(
+                "{} allocs, {} allocated, {} heap peak",
+                allocs.allocations,
+                bytes(allocs.bytes),
+                bytes(allocs.peak)
+            ));
+        }
+        if let Some(resident) = self.resident.filter(|_| show_allocs) {
+            details.push(format!("{} resident peak", bytes(resident)));
+        }
+        let details = if details.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", details.join(", "))
+        };
+        match (self.status, &self.answer, self.expected) {
+            (Status::Wrong, Some(answer), Some(expected)) => {
+                println!("{label}{stage}: wrong answer{details}");
+                print!("{}", aoc::diff(expected, answer));
+            }
+            (_, Some(answer), _) => println!("{label}{stage}: {answer}{details}"),
+            (status, None, _) => println!("{label}{stage}: {status}{details}"),
+        }
+        for reason in &self.reasons {
+            eprintln!("{label}{stage}: {reason}");
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "day": self.day,
+            "input": self.input,
+            "part": self.stage.to_string(),
+            "status": self.status.to_string(),
+            "answer": self.answer,
+            "expected": self.expected,
+            "time_ns": self.time.map(|time| time.as_nanos()),
+            "allocs": self.allocs.map(stats_to_json),
+            "resident_peak": self.resident,
+            "reference_ns": self.reference.map(|time| time.as_nanos()),
+            "reasons": self.reasons,
+        })
+    }
+}
+
+/// How solving a whole day went, for the summary of `--all`.
+#[derive(Debug, Clone)]
+pub struct DayRun {
+    pub day: String,
+    pub time: Duration,
+    /// The message of the panic that stopped the day, if any.
+    pub panic: Option<String>,
+}
+
+/// Sums up how a part did on every input it was solved with: the first
+/// failure if any, and the total time.
+fn summarize(reports: &[Report], run: &DayRun, part: Part) -> String {
+    let reports: Vec<_> = reports
+        .iter()
+        .filter(|report| report.day == run.day && report.stage == Stage::Part(part))
+        .collect();
+    let Some(last) = reports.last() else {
+        return if run.panic.is_some() { "panicked" } else { "-" }.to_owned();
+    };
+    let status = reports
+        .iter()
+        .map(|report| report.status)
+        .find(|status| status.is_failure())
+        .unwrap_or(last.status);
+    let time: Duration = reports.iter().filter_map(|report| report.time).sum();
+    format!("{status} {time:.1?}")
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Lines for humans, printed as soon as each part is solved.
+    #[default]
+    Text,
+    /// A JSON array with an object per [`Report`], printed at the end.
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown format `{s}`, expected `text` or `json`")),
+        }
+    }
+}
+
+/// Collects the reports, showing them in the given format.
+#[derive(Debug)]
+pub struct Reporter {
+    format: Format,
+    /// Whether to show the allocations in text, which JSON always has.
+    show_allocs: bool,
+    reports: Vec<Report>,
+}
+
+impl Reporter {
+    pub fn new(format: Format, show_allocs: bool) -> Self {
+        Reporter {
+            format,
+            show_allocs,
+            reports: Vec::new(),
+        }
+    }
+
+    pub fn add(&mut self, report: Report) {
+        if self.format == Format::Text {
+            report.print_text(self.show_allocs);
+        }
+        self.reports.push(report);
+    }
+
+    /// Starts the reports of a new day.
+    pub fn header(&self, day: &str) {
+        if self.format == Format::Text {
+            println!("{day}");
+        }
+    }
+
+    /// Prints a table with how every part of the days did, and how long it all
+    /// took.
+    pub fn summary(&self, runs: &[DayRun], total: Duration) {
+        if self.format != Format::Text {
+            return;
+        }
+        println!();
+        println!(
+            "{:<10} {:<20} {:<20} {:>10}",
+            "day", "part1", "part2", "time"
+        );
+        for run in runs {
+            let [part1, part2] = Part::ALL.map(|part| summarize(&self.reports, run, part));
+            let time = format!("{:.1?}", run.time);
+            println!("{:<10} {part1:<20} {part2:<20} {time:>10}", run.day);
+        }
+        let total = format!("{total:.1?}");
+        println!("{:<10} {:<20} {:<20} {total:>10}", "total", "", "");
+        for run in runs {
+            if let Some(message) = &run.panic {
+                println!("{} panicked: {message}", run.day);
+            }
+        }
+    }
+
+    /// Prints whatever wasn't printed as it came. Returns whether no part
+    /// failed.
+    pub fn finish(self) -> bool {
+        if self.format == Format::Json {
+            let reports: Vec<_> = self.reports.iter().map(Report::to_json).collect();
+            println!("{}", Value::Array(reports));
+        }
+        !self.reports.iter().any(|report| report.status.is_failure())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(input: &str, part: Part, status: Status, micros: u64) -> Report {
+        Report {
+            day: "23/day04".to_owned(),
+            input: input.to_owned(),
+            stage: Stage::Part(part),
+            status,
+            answer: Some("13".to_owned()),
+            expected: None,
+            time: Some(Duration::from_micros(micros)),
+            allocs: None,
+            resident: None,
+            reference: None,
+            reasons: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_summarize() {
+        let reports = [
+            report("example1", Part::One, Status::Ok, 10),
+            report("example1", Part::Two, Status::Wrong, 20),
+            report("example2", Part::One, Status::Unverified, 5),
+            report("example2", Part::Two, Status::Ok, 20),
+        ];
+        let mut run = DayRun {
+            day: "23/day04".to_owned(),
+            time: Duration::ZERO,
+            panic: None,
+        };
+        assert_eq!(summarize(&reports, &run, Part::One), "unverified 15.0µs");
+        assert_eq!(summarize(&reports, &run, Part::Two), "wrong 40.0µs");
+        assert_eq!(summarize(&reports[..1], &run, Part::Two), "-");
+        run.panic = Some("oops".to_owned());
+        assert_eq!(summarize(&reports[..1], &run, Part::Two), "panicked");
+    }
+
+    #[test]
+    fn test_json() {
+        let report = Report {
+            day: "23/day04".to_owned(),
+            input: "example1".to_owned(),
+            stage: Stage::Variant(Part::Two, "naive"),
+            status: Status::Wrong,
+            answer: Some("31".to_owned()),
+            expected: Some("30"),
+            time: Some(Duration::from_micros(12)),
+            allocs: Some(Stats {
+                allocations: 2,
+                bytes: 64,
+                peak: 48,
+            }),
+            resident: Some(4096),
+            reference: Some(Duration::from_micros(3)),
+            reasons: Vec::new(),
+        };
+        assert_eq!(
+            report.to_json().to_string(),
+            r#"{"allocs":{"allocations":2,"bytes":64,"heap_peak":48},"answer":"31","day":"23/day04","expected":"30","input":"example1","part":"part2 naive","reasons":[],"reference_ns":3000,"resident_peak":4096,"status":"wrong","time_ns":12000}"#
+        );
+    }
+
+    #[test]
+    fn test_bytes() {
+        assert_eq!(bytes(1023), "1023 B");
+        assert_eq!(bytes(1536), "1.5 KiB");
+        assert_eq!(bytes(3 * 1024 * 1024 + 1), "3.0 MiB");
+        assert_eq!(bytes(u64::MAX), "16777215.9 TiB");
+    }
+}
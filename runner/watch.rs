@@ -0,0 +1,181 @@
+//! Solves a day again whenever its code, input or examples (or the shared
+//! library) change.
+
+use std::{
+    env,
+    ffi::{OsStr, OsString},
+    path::Path,
+    process::Command,
+    sync::mpsc::{self, RecvTimeoutError},
+    time::Duration,
+};
+
+use notify::{EventKind, RecursiveMode, Watcher};
+
+use crate::{report::Format, root, Day, Options};
+
+/// How long to wait for more changes before solving, since editors often save
+/// a file in several steps.
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Whether a change to the file should trigger a new run. Editors' swap and
+/// backup files are ignored.
+fn watched(path: &Path) -> bool {
+    let stem = path.file_stem().and_then(OsStr::to_str).unwrap_or_default();
+    match path.extension().and_then(OsStr::to_str).unwrap_or_default() {
+        "rs" => !stem.starts_with('.'),
+        "txt" => stem == "input" || stem.starts_with("example"),
+        "toml" => stem == "Cargo",
+        _ => false,
+    }
+}
+
+/// The arguments that solve the day (or its examples) with the same run
+/// options.
+fn args(day: &Day, options: &Options, example: bool) -> Vec<String> {
+    let mut args = vec![
+        "--year".to_owned(),
+        day.year.to_string(),
+        "--day".to_owned(),
+        day.day.to_string(),
+    ];
+    if example {
+        args.push("--example".to_owned());
+    }
+    if options.format == Format::Json {
+        args.extend(["--format".to_owned(), "json".to_owned()]);
+    }
+    for (name, value) in &options.params {
+        args.extend(["--param".to_owned(), format!("{name}={value}")]);
+    }
+    if let Some(timeout) = options.timeout {
+        args.extend(["--timeout".to_owned(), timeout.as_secs_f64().to_string()]);
+    }
+    if let Some(memory) = options.memory {
+        args.extend(["--memory".to_owned(), memory.to_string()]);
+    }
+    if let Some(progress) = options.progress {
+        args.extend(["--progress".to_owned(), progress.to_string()]);
+    }
+    let flags = [
+        (options.in_process, "--in-process"),
+        (options.allocs, "--allocs"),
+        (options.variants, "--variants"),
+        (options.no_checkpoint, "--no-checkpoint"),
+    ];
+    for (set, flag) in flags {
+        if set {
+            args.push(flag.to_owned());
+        }
+    }
+    args
+}
+
+/// Rebuilds the runner and solves the examples, then the real input if they
+/// pass (and `--example` wasn't given).
+fn solve(day: &Day, options: &Options) {
+    // Clear the screen, so only the latest run is shown.
+    print!("\x1b[2J\x1b[H");
+    println!("{day}");
+
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo"));
+    let run = |example| {
+        Command::new(&cargo)
+            .current_dir(root())
+            .args(["run", "--release", "--quiet", "--package", "runner", "--"])
+            .args(args(day, options, example))
+            .status()
+            .is_ok_and(|status| status.success())
+    };
+    if !day.solution.examples.is_empty() && !run(true) {
+        return;
+    }
+    if !options.example {
+        run(false);
+    }
+}
+
+/// Watches the day's directory and the shared library, solving the day on
+/// every change. Only returns on errors.
+pub fn watch(day: &Day, options: &Options) -> Result<(), String> {
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).map_err(|e| e.to_string())?;
+    for dir in [day.dir(), root().join("aoc")] {
+        watcher
+            .watch(&dir, RecursiveMode::NonRecursive)
+            .map_err(|e| e.to_string())?;
+    }
+
+    loop {
+        solve(day, options);
+        // Wait for a relevant change, then for the dust to settle.
+        loop {
+            let event = rx
+                .recv()
+                .map_err(|e| e.to_string())?
+                .map_err(|e| e.to_string())?;
+            let changed = matches!(
+                event.kind,
+                EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+            );
+            if changed && event.paths.iter().any(|path| watched(path)) {
+                break;
+            }
+        }
+        loop {
+            match rx.recv_timeout(DEBOUNCE) {
+                Ok(_) => {}
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return Err("watcher stopped".to_owned()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{find, Command, DAYS};
+
+    #[test]
+    fn test_watched() {
+        for path in ["23/day04/lib.rs", "input.txt", "example2.txt", "Cargo.toml"] {
+            assert!(watched(Path::new(path)), "{path}");
+        }
+        for path in [".lib.rs.swp", "lib.rs~", "4913", "notes.md", "23/day04"] {
+            assert!(!watched(Path::new(path)), "{path}");
+        }
+    }
+
+    #[test]
+    fn test_args() {
+        let options = Options::parse(
+            [
+                "watch",
+                "--day=4",
+                "--year=23",
+                "--format=json",
+                "--param=red=1",
+                "--timeout=0.5",
+                "--memory=64",
+                "--progress=log",
+                "--in-process",
+                "--allocs",
+                "--variants",
+                "--no-checkpoint",
+            ]
+            .map(str::to_owned),
+        )
+        .unwrap();
+        let day = find(DAYS, Some(23), 4).unwrap();
+        let rerun = Options::parse(args(day, &options, true)).unwrap();
+        assert_eq!(
+            rerun,
+            Options {
+                command: Command::Run,
+                example: true,
+                ..options
+            }
+        );
+    }
+}
@@ -0,0 +1,39 @@
+static INPUT: &str = include_str!("input.txt");
+
+fn part1(_input: &str) -> u32 {
+    0
+}
+
+fn part2(_input: &str) -> u32 {
+    0
+}
+
+pub static SOLUTION: aoc::Solution = aoc::Solution::new(
+    INPUT,
+    |input, _| part1(input).to_string(),
+    |input, _| part2(input).to_string(),
+)
+.with_examples(&[aoc::Example {
+    input: include_str!("example1.txt"),
+    answers: [None, None],
+}]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        aoc::check_examples(&SOLUTION, aoc::Part::One);
+    }
+
+    #[test]
+    fn test_part2() {
+        aoc::check_examples(&SOLUTION, aoc::Part::Two);
+    }
+
+    #[test]
+    fn test_answers() {
+        aoc::check_answers(&SOLUTION);
+    }
+}